@@ -0,0 +1,199 @@
+//! Prints names the plugin generates without running swc.
+//!
+//! ```sh
+//! swc-css-modules [--config <json|file>] [--cwd <dir>] name <stylesheet> <local>...
+//! swc-css-modules [--config <json|file>] [--cwd <dir>] manifest <stylesheet>
+//! swc-css-modules [--config <json|file>] [--cwd <dir>] css <stylesheet>
+//! swc-css-modules [--cwd <dir>] diff <config-a> <config-b> <stylesheet>...
+//! swc-css-modules [--config <json|file>] [--cwd <dir>] unused <usage-file> <stylesheet>...
+//! ```
+//!
+//! Exit codes: `0` on success, `1` if `diff` found differences or `unused`
+//! found unused classes, `2` on errors.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::ExitCode;
+use std::{env, fs};
+
+use path_absolutize::*;
+use swc_plugin_css_modules::{Config, Injector, Usage};
+
+const USAGE: &str = "\
+Usage:
+  swc-css-modules [options] name <stylesheet> <local>...
+  swc-css-modules [options] manifest <stylesheet>
+  swc-css-modules [options] css <stylesheet>
+  swc-css-modules [options] diff <config-a> <config-b> <stylesheet>...
+  swc-css-modules [options] unused <usage-file> <stylesheet>...
+
+Options:
+  --config <json|file>  plugin options as JSON or path to a JSON file
+  --cwd <dir>           directory to resolve paths from, defaults to the current one";
+
+fn main() -> ExitCode {
+    match run(env::args().skip(1).collect()) {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<ExitCode, String> {
+    let mut config = None;
+    let mut cwd = None;
+    let mut positional = vec![];
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" => config = Some(args.next().ok_or("--config requires a value")?),
+            "--cwd" => cwd = Some(args.next().ok_or("--cwd requires a value")?),
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(ExitCode::SUCCESS);
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option {arg}\n\n{USAGE}")),
+            _ => positional.push(arg),
+        }
+    }
+
+    let cwd = match cwd {
+        // Names are hashed from paths relative to the absolute cwd
+        Some(cwd) => Path::new(&cwd)
+            .absolutize()
+            .map_err(|error| error.to_string())?
+            .to_path_buf(),
+        None => env::current_dir().map_err(|error| error.to_string())?,
+    };
+
+    let Some((command, args)) = positional.split_first() else {
+        return Err(USAGE.to_string());
+    };
+
+    match (command.as_str(), args) {
+        ("name", [stylesheet, locals @ ..]) if !locals.is_empty() => {
+            let stylesheet = cwd.join(stylesheet);
+            let mut injector = injector(&cwd, &stylesheet, config.as_deref())?;
+
+            for local in locals {
+                println!("{local}\t{}", injector.name(&stylesheet, local));
+            }
+
+            Ok(ExitCode::SUCCESS)
+        }
+
+        ("manifest", [stylesheet]) => {
+            let manifest = manifest(&cwd, &cwd.join(stylesheet), config.as_deref())?;
+
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&manifest).map_err(|error| error.to_string())?
+            );
+
+            Ok(ExitCode::SUCCESS)
+        }
+
+        ("css", [stylesheet]) => {
+            let stylesheet = cwd.join(stylesheet);
+            let source = fs::read_to_string(&stylesheet).map_err(|error| {
+                format!("Cannot read {}: {error}", stylesheet.to_string_lossy())
+            })?;
+
+            let mut injector = injector(&cwd, &stylesheet, config.as_deref())?;
+            print!("{}", injector.rewrite(&stylesheet, &source));
+
+            Ok(ExitCode::SUCCESS)
+        }
+
+        ("diff", [a, b, stylesheets @ ..]) if !stylesheets.is_empty() => {
+            let mut differs = false;
+
+            for stylesheet in stylesheets {
+                let path = cwd.join(stylesheet);
+                let a = manifest(&cwd, &path, Some(a))?;
+                let mut b = manifest(&cwd, &path, Some(b))?;
+
+                for (local, name_a) in a {
+                    let name_b = b.remove(&local).unwrap_or_default();
+
+                    if name_a != name_b {
+                        differs = true;
+                        println!("{stylesheet}\t{local}\t{name_a}\t{name_b}");
+                    }
+                }
+
+                // Names exported only with the second config
+                for (local, name_b) in b {
+                    differs = true;
+                    println!("{stylesheet}\t{local}\t\t{name_b}");
+                }
+            }
+
+            Ok(ExitCode::from(u8::from(differs)))
+        }
+
+        ("unused", [usage_file, stylesheets @ ..]) if !stylesheets.is_empty() => {
+            let usage_file = cwd.join(usage_file);
+            let usage = Usage::read(&usage_file).map_err(|error| {
+                format!("Cannot read {}: {error}", usage_file.to_string_lossy())
+            })?;
+
+            let mut found = false;
+
+            for stylesheet in stylesheets {
+                let path = cwd.join(stylesheet);
+                let unused = injector(&cwd, &path, config.as_deref())?
+                    .unused(&usage, &path)
+                    .ok_or_else(|| format!("Cannot read {}", path.to_string_lossy()))?;
+
+                for class in unused {
+                    found = true;
+                    println!("{stylesheet}\t{class}");
+                }
+            }
+
+            Ok(ExitCode::from(u8::from(found)))
+        }
+
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// Reads plugin options from inline JSON or a JSON file.
+/// Config files are relative to the cwd.
+fn read_config(cwd: &Path, config: Option<&str>) -> Result<Config, String> {
+    let json = match config {
+        None => "{}".to_string(),
+        Some(json) if json.trim_start().starts_with('{') => json.to_string(),
+        Some(path) => fs::read_to_string(cwd.join(path))
+            .map_err(|error| format!("Cannot read {path}: {error}"))?,
+    };
+
+    let config: Config =
+        serde_json::from_str(&json).map_err(|error| format!("Invalid config: {error}"))?;
+
+    config.validate();
+
+    Ok(config)
+}
+
+fn injector(cwd: &Path, stylesheet: &Path, config: Option<&str>) -> Result<Injector, String> {
+    Ok(Injector::new(
+        &cwd.to_string_lossy(),
+        &stylesheet.to_string_lossy(),
+        read_config(cwd, config)?,
+    ))
+}
+
+fn manifest(
+    cwd: &Path,
+    stylesheet: &Path,
+    config: Option<&str>,
+) -> Result<BTreeMap<String, String>, String> {
+    injector(cwd, stylesheet, config)?
+        .manifest(stylesheet)
+        .ok_or_else(|| format!("Cannot read {}", stylesheet.to_string_lossy()))
+}
@@ -1,8 +1,230 @@
+use std::collections::BTreeMap;
+
 use serde::Deserialize;
 use serde_inline_default::serde_inline_default;
+use swc_core::common::DUMMY_SP;
 use ts_rs::TS;
 
-// TODO: locals_convention
+use crate::diagnostics::{self, Kind};
+
+/// Style of exported class names like `exportLocalsConvention` of
+/// [css-loader](https://github.com/webpack-contrib/css-loader#exportlocalsconvention).
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, TS)]
+#[serde(rename_all = "kebab-case")]
+#[ts(export, export_to = "types.d.ts")]
+pub enum LocalsConvention {
+    /// Class names will be exported as is.
+    #[default]
+    AsIs,
+
+    /// Class names will be camelized, the original class name will not to be removed.
+    CamelCase,
+
+    /// Class names will be camelized, the original class name will be removed.
+    CamelCaseOnly,
+
+    /// Only dashes in class names will be camelized.
+    Dashes,
+
+    /// Dashes in class names will be camelized, the original class name will be removed.
+    DashesOnly,
+}
+
+impl LocalsConvention {
+    /// Returns names under which the class is exported to JS.
+    pub fn keys(self, name: &str) -> Vec<String> {
+        // Custom properties are always exported as is
+        if name.starts_with("--") {
+            return vec![name.to_string()];
+        }
+
+        let mut keys = match self {
+            Self::AsIs => vec![name.to_string()],
+            Self::CamelCase => vec![name.to_string(), camel_case(name)],
+            Self::CamelCaseOnly => vec![camel_case(name)],
+            Self::Dashes => vec![name.to_string(), dashes_camel_case(name)],
+            Self::DashesOnly => vec![dashes_camel_case(name)],
+        };
+
+        keys.dedup();
+        keys
+    }
+
+    /// Returns the convention allowed with `named_export`, only camelized
+    /// names like css-loader does, `"as-is"` defaults to `"camel-case-only"`.
+    pub fn named_export(self) -> Self {
+        match self {
+            Self::AsIs | Self::CamelCase => Self::CamelCaseOnly,
+            Self::Dashes => Self::DashesOnly,
+            convention => convention,
+        }
+    }
+}
+
+/// `Component__header--level-1` → `componentHeaderLevel1`
+fn camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut upper_next = false;
+
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.' | ' ') {
+            upper_next = !result.is_empty();
+        } else if upper_next {
+            result.extend(c.to_uppercase());
+            upper_next = false;
+        } else if result.is_empty() {
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// `Component__header--level-1` → `Component__headerLevel1`
+fn dashes_camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut chars = name.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '-' {
+            result.push(c);
+            continue;
+        }
+
+        while chars.peek() == Some(&'-') {
+            chars.next();
+        }
+
+        match chars.peek() {
+            Some(next) if next.is_alphanumeric() || *next == '_' => {
+                result.extend(next.to_uppercase());
+                chars.next();
+            }
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// What names are hashed from besides the local name.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, TS)]
+#[serde(rename_all = "kebab-case")]
+#[ts(export, export_to = "types.d.ts")]
+pub enum HashInput {
+    /// The path of the stylesheet relative to the root.
+    #[default]
+    Path,
+
+    /// The content of the stylesheet, names do not change when it is moved.
+    Content,
+
+    /// The path of the stylesheet in its package prefixed by the package name.
+    Package,
+}
+
+/// What globs of an override are matched against.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, TS)]
+#[serde(rename_all = "kebab-case")]
+#[ts(export, export_to = "types.d.ts")]
+pub enum OverrideTarget {
+    /// The file importing stylesheets.
+    #[default]
+    File,
+
+    /// The imported stylesheet.
+    Stylesheet,
+}
+
+/// Options for files matching globs relative to `root`.
+#[derive(Debug, Deserialize, Clone, TS)]
+#[ts(export, export_to = "types.d.ts")]
+pub struct Override {
+    pub files: Vec<String>,
+
+    #[serde(default)]
+    pub target: OverrideTarget,
+
+    #[ts(optional)]
+    pub generate_scoped_name: Option<String>,
+
+    #[ts(optional)]
+    pub hash_prefix: Option<String>,
+
+    /// Applied only for importing files.
+    #[ts(optional)]
+    pub css_modules_suffix: Option<String>,
+
+    /// Applied only for importing files.
+    #[ts(optional)]
+    pub strict: Option<bool>,
+}
+
+/// What to do when the plugin finds a problem.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, TS)]
+#[serde(rename_all = "kebab-case")]
+#[ts(export, export_to = "types.d.ts")]
+pub enum Severity {
+    Off,
+    Warn,
+    Error,
+}
+
+/// Severities of diagnostics, defaults are used for missing ones.
+#[derive(Debug, Deserialize, Clone, Default, TS)]
+#[ts(export, export_to = "types.d.ts")]
+pub struct Diagnostics {
+    /// `styles[prefix + "title"]`, `error` if `strict`, otherwise `warn`.
+    #[ts(optional)]
+    pub computed_access: Option<Severity>,
+
+    /// `fn(styles)`, `error` if `strict`, otherwise `warn`.
+    #[ts(optional)]
+    pub escaping_binding: Option<Severity>,
+
+    /// `styles.title` when the stylesheet does not export `title`, `off` by default.
+    #[ts(optional)]
+    pub unknown_class: Option<Severity>,
+
+    /// `styles.title` when `title` is used only in `:global` context, `warn` by default.
+    #[ts(optional)]
+    pub global_class: Option<Severity>,
+
+    /// Different names generated to the same name, `off` by default.
+    #[ts(optional)]
+    pub collision: Option<Severity>,
+
+    /// A stylesheet which cannot be read or parsed, `error` if `strict`, otherwise `warn`.
+    #[ts(optional)]
+    pub unreadable_stylesheet: Option<Severity>,
+
+    /// `@value a: b; @value b: a;`, `error` by default.
+    #[ts(optional)]
+    pub cyclic_value: Option<Severity>,
+
+    /// `import styles from "./a.css"` with `named_export`, `error` by default.
+    #[ts(optional)]
+    pub default_import: Option<Severity>,
+
+    /// `hash_input` falling back to the path, `warn` by default.
+    #[ts(optional)]
+    pub hash_input: Option<Severity>,
+
+    /// Usage file, declarations or tsconfig which cannot be read or written, `warn` by default.
+    #[ts(optional)]
+    pub file_access: Option<Severity>,
+
+    /// Incompatible options like `"locals_convention": "dashes"` with `named_export` and invalid
+    /// globs of overrides, `error` by default.
+    #[ts(optional)]
+    pub invalid_config: Option<Severity>,
+
+    /// `kit/Card.module.css` when `exports` of `kit` do not export it, `warn` by default.
+    #[ts(optional)]
+    pub unexported_subpath: Option<Severity>,
+}
 
 #[serde_inline_default]
 #[derive(Debug, Deserialize, Clone, TS)]
@@ -14,9 +236,172 @@ pub struct Config {
     #[serde_inline_default("".to_string())]
     pub hash_prefix: String,
 
+    #[serde(default)]
+    pub hash_input: HashInput,
+
     #[serde_inline_default(".css".to_string())]
     pub css_modules_suffix: String,
 
     #[serde_inline_default("".to_string())]
     pub root: String,
+
+    /// Pattern for `@keyframes` names, `generate_scoped_name` if not set.
+    #[ts(optional)]
+    pub keyframes_scoped_name: Option<String>,
+
+    /// Pattern for custom properties, `generate_scoped_name` if not set.
+    #[ts(optional)]
+    pub custom_property_scoped_name: Option<String>,
+
+    /// Pattern for grid line and area names, `generate_scoped_name` if not set.
+    #[ts(optional)]
+    pub grid_scoped_name: Option<String>,
+
+    /// Pattern for container names, `generate_scoped_name` if not set.
+    #[ts(optional)]
+    pub container_scoped_name: Option<String>,
+
+    /// Scope custom properties keeping the leading `--`.
+    #[serde_inline_default(false)]
+    pub dashed_idents: bool,
+
+    #[serde(default)]
+    pub locals_convention: LocalsConvention,
+
+    /// Stylesheets are imported by names like css-loader `namedExport` does.
+    #[serde_inline_default(false)]
+    pub named_export: bool,
+
+    /// Prefix of reserved words like `class` exported with `named_export`.
+    #[serde_inline_default("_".to_string())]
+    pub named_export_prefix: String,
+
+    /// Write `.d.ts` next to every imported stylesheet.
+    #[serde_inline_default(false)]
+    pub emit_declarations: bool,
+
+    /// Emit errors instead of warnings for usages which cannot be inlined.
+    #[serde_inline_default(false)]
+    pub strict: bool,
+
+    #[serde(default)]
+    pub diagnostics: Diagnostics,
+
+    /// Modules of class helpers bound to stylesheets like `classNames.bind(styles)`.
+    #[serde_inline_default(vec!["classnames/bind".to_string()])]
+    pub bound_class_helpers: Vec<String>,
+
+    /// Fold templates and calls of class joining helpers with constant arguments to strings.
+    #[serde_inline_default(false)]
+    pub fold_constants: bool,
+
+    /// Modules of class joining helpers folded by `fold_constants`.
+    #[serde_inline_default(vec!["clsx".to_string(), "clsx/lite".to_string(), "classnames".to_string()])]
+    pub class_join_helpers: Vec<String>,
+
+    /// Aliases of import specifiers like `resolve.alias` of webpack, targets
+    /// are relative to `root`.
+    #[serde(default)]
+    pub alias: BTreeMap<String, String>,
+
+    /// Resolve imports with `baseUrl` and `paths` of the tsconfig, relative to `root`.
+    #[ts(optional)]
+    pub tsconfig: Option<String>,
+
+    /// Resolve symlinks in paths of stylesheets before hashing.
+    #[serde_inline_default(false)]
+    pub canonicalize: bool,
+
+    /// Directories of packages relative to `root`, their stylesheets are
+    /// hashed as `<package>/<path in the package>`.
+    #[serde(default)]
+    pub package_roots: BTreeMap<String, String>,
+
+    /// Append locals accessed from JS to the file, relative to `root`.
+    #[ts(optional)]
+    pub usage_file: Option<String>,
+
+    /// Options for files matching globs, later overrides win.
+    #[serde(default)]
+    pub overrides: Vec<Override>,
+}
+
+impl Config {
+    /// Reports options which are not allowed together as `invalid_config`.
+    ///
+    /// Entrypoints call it once after parsing the config.
+    pub fn validate(&self) {
+        if !self.named_export {
+            return;
+        }
+
+        let (name, only) = match self.locals_convention {
+            LocalsConvention::CamelCase => ("camel-case", "camel-case-only"),
+            LocalsConvention::Dashes => ("dashes", "dashes-only"),
+            _ => return,
+        };
+
+        diagnostics::report(
+            self,
+            Kind::InvalidConfig,
+            DUMMY_SP,
+            &format!(
+                "locals_convention \"{}\" is not allowed with named_export, use \"{}\"",
+                name, only
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LocalsConvention;
+
+    const NAME: &str = "Component__header--level-1";
+
+    #[test]
+    fn locals_convention() {
+        assert_eq!(LocalsConvention::AsIs.keys(NAME), vec![NAME]);
+        assert_eq!(
+            LocalsConvention::CamelCase.keys(NAME),
+            vec![NAME, "componentHeaderLevel1"]
+        );
+        assert_eq!(
+            LocalsConvention::CamelCaseOnly.keys(NAME),
+            vec!["componentHeaderLevel1"]
+        );
+        assert_eq!(
+            LocalsConvention::Dashes.keys(NAME),
+            vec![NAME, "Component__headerLevel1"]
+        );
+        assert_eq!(
+            LocalsConvention::DashesOnly.keys(NAME),
+            vec!["Component__headerLevel1"]
+        );
+        assert_eq!(LocalsConvention::CamelCase.keys("root"), vec!["root"]);
+        assert_eq!(
+            LocalsConvention::CamelCaseOnly.keys("--brand"),
+            vec!["--brand"]
+        );
+    }
+
+    #[test]
+    fn named_export() {
+        assert_eq!(
+            LocalsConvention::AsIs.named_export(),
+            LocalsConvention::CamelCaseOnly
+        );
+        assert_eq!(
+            LocalsConvention::CamelCase.named_export(),
+            LocalsConvention::CamelCaseOnly
+        );
+        assert_eq!(
+            LocalsConvention::Dashes.named_export(),
+            LocalsConvention::DashesOnly
+        );
+        assert_eq!(
+            LocalsConvention::DashesOnly.named_export(),
+            LocalsConvention::DashesOnly
+        );
+    }
 }
@@ -0,0 +1,98 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::identifier::is_identifier;
+
+/// Returns the path of TypeScript declaration for the stylesheet.
+///
+/// `Button.module.css` → `Button.module.css.d.ts`
+pub fn declaration_path(filepath: &Path) -> PathBuf {
+    let mut path = filepath.as_os_str().to_owned();
+    path.push(".d.ts");
+
+    PathBuf::from(path)
+}
+
+/// Returns TypeScript declaration of the css module exporting the names.
+///
+/// With `named_export` there is no default export, like css-loader does.
+pub fn render(names: &[String], named_export: bool) -> String {
+    if named_export {
+        return names
+            .iter()
+            .filter(|name| is_identifier(name))
+            .map(|name| format!("export const {name}: string;\n"))
+            .collect();
+    }
+
+    let mut declaration = String::from("declare const styles: {\n");
+
+    for name in names {
+        declaration.push_str(&format!("  readonly {}: string;\n", property_key(name)));
+    }
+
+    declaration.push_str("};\n\nexport default styles;\n");
+
+    let named: Vec<_> = names.iter().filter(|name| is_identifier(name)).collect();
+
+    if !named.is_empty() {
+        declaration.push('\n');
+
+        for name in named {
+            declaration.push_str(&format!("export const {name}: string;\n"));
+        }
+    }
+
+    declaration
+}
+
+/// Writes the declaration next to the stylesheet.
+///
+/// The file is not touched if it is up to date, so watchers are not triggered.
+pub fn write(filepath: &Path, declaration: &str) -> io::Result<()> {
+    let path = declaration_path(filepath);
+
+    if fs::read_to_string(&path).is_ok_and(|current| current == declaration) {
+        return Ok(());
+    }
+
+    fs::write(path, declaration)
+}
+
+fn property_key(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_string()
+    } else {
+        format!("{name:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+
+    #[test]
+    fn declaration() {
+        assert_eq!(
+            render(&["is-active".to_string(), "root".to_string()], false),
+            r#"declare const styles: {
+  readonly "is-active": string;
+  readonly root: string;
+};
+
+export default styles;
+
+export const root: string;
+"#
+        );
+    }
+
+    #[test]
+    fn named_export() {
+        assert_eq!(
+            render(&["_class".to_string(), "isActive".to_string()], true),
+            "export const _class: string;\nexport const isActive: string;\n"
+        );
+    }
+}
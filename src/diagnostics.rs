@@ -0,0 +1,79 @@
+use swc_core::common::Span;
+use swc_core::plugin::errors::HANDLER;
+
+use crate::config::{Config, Severity};
+
+/// Diagnostics with configurable severity.
+#[derive(Debug, Clone, Copy)]
+pub enum Kind {
+    ComputedAccess,
+    EscapingBinding,
+    UnknownClass,
+    GlobalClass,
+    Collision,
+    UnreadableStylesheet,
+    CyclicValue,
+    DefaultImport,
+    HashInput,
+    FileAccess,
+    InvalidConfig,
+    UnexportedSubpath,
+}
+
+impl Kind {
+    /// Returns the configured severity or the default one.
+    pub fn severity(self, config: &Config) -> Severity {
+        let diagnostics = &config.diagnostics;
+        let inline = if config.strict {
+            Severity::Error
+        } else {
+            Severity::Warn
+        };
+
+        match self {
+            Self::ComputedAccess => diagnostics.computed_access.unwrap_or(inline),
+            Self::EscapingBinding => diagnostics.escaping_binding.unwrap_or(inline),
+            Self::UnknownClass => diagnostics.unknown_class.unwrap_or(Severity::Off),
+            Self::GlobalClass => diagnostics.global_class.unwrap_or(Severity::Warn),
+            Self::Collision => diagnostics.collision.unwrap_or(Severity::Off),
+            Self::UnreadableStylesheet => diagnostics.unreadable_stylesheet.unwrap_or(inline),
+            Self::CyclicValue => diagnostics.cyclic_value.unwrap_or(Severity::Error),
+            Self::DefaultImport => diagnostics.default_import.unwrap_or(Severity::Error),
+            Self::HashInput => diagnostics.hash_input.unwrap_or(Severity::Warn),
+            Self::FileAccess => diagnostics.file_access.unwrap_or(Severity::Warn),
+            Self::InvalidConfig => diagnostics.invalid_config.unwrap_or(Severity::Error),
+            Self::UnexportedSubpath => diagnostics.unexported_subpath.unwrap_or(Severity::Warn),
+        }
+    }
+}
+
+/// Emits the diagnostic with the configured severity.
+pub fn report(config: &Config, kind: Kind, span: Span, message: &str) {
+    match kind.severity(config) {
+        Severity::Off => {}
+        Severity::Warn => warning(span, message),
+        Severity::Error => error(span, message),
+    }
+}
+
+/// Emits the error.
+///
+/// Outside of swc, e.g. in the CLI, the error is printed to stderr.
+fn error(span: Span, message: &str) {
+    if HANDLER.is_set() {
+        HANDLER.with(|handler| handler.struct_span_err(span, message).emit());
+    } else {
+        eprintln!("error: {message}");
+    }
+}
+
+/// Emits the warning.
+///
+/// Outside of swc, e.g. in the CLI, the warning is printed to stderr.
+fn warning(span: Span, message: &str) {
+    if HANDLER.is_set() {
+        HANDLER.with(|handler| handler.struct_span_warn(span, message).emit());
+    } else {
+        eprintln!("warning: {message}");
+    }
+}
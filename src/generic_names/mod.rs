@@ -3,6 +3,7 @@ use lazy_static::lazy_static;
 use regex::Regex;
 use std::{env, path::PathBuf};
 
+use crate::hash_path;
 use crate::loader_utils::interpolate::{
     interpolate_name, LoaderContext, Options as LoaderUtilsOptions,
 };
@@ -48,32 +49,42 @@ impl Generator {
     /// # Examples
     ///
     /// ```
-    /// use swc_plugin_css_modules::generic_names::Generator;
+    /// use swc_plugin_css_modules::generic_names::{Generator, Options};
     ///
-    /// let generator = Generator::new("[name]__[local]___[hash:base64:5]");
+    /// let generator = Generator::new_with_options(
+    ///     "[name]__[local]___[hash:base64:5]",
+    ///     Options {
+    ///         context: "/case".into(),
+    ///         hash_prefix: String::new(),
+    ///     },
+    /// );
     ///
     /// assert_eq!(
     ///     generator.generate("foo", "/case/source.css".into()),
-    ///     "source__foo___pdq35".to_string(),
+    ///     "source__foo___sDjeQ".to_string(),
     /// );
     /// ```
     pub fn generate(&self, local_name: &str, filepath: PathBuf) -> String {
+        let relative_path = hash_path::relative(
+            &filepath.to_string_lossy(),
+            &self.options.context.to_string_lossy(),
+        );
+
+        self.generate_from(local_name, filepath, &relative_path)
+    }
+
+    /// Generates the name hashing the input instead of the path of the file
+    /// relative to the context.
+    pub fn generate_from(&self, local_name: &str, filepath: PathBuf, input: &str) -> String {
         let name = self.pattern.replace("[local]", local_name);
 
-        let absolute_path = pathdiff::diff_paths(filepath.clone(), &self.options.context)
-            .unwrap()
-            .to_str()
-            .unwrap()
-            .replace('\\', r"/");
+        let filepath = hash_path::normalize(&filepath.to_string_lossy());
 
-        let content = format!(
-            "{}{}\x00{}",
-            self.options.hash_prefix, absolute_path, local_name
-        );
+        let content = format!("{}{}\x00{}", self.options.hash_prefix, input, local_name);
 
         let generic_name = interpolate_name(
             LoaderContext {
-                resource_path: Some(filepath),
+                resource_path: Some(PathBuf::from(filepath)),
             },
             name.as_str(),
             LoaderUtilsOptions {
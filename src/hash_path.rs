@@ -0,0 +1,325 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use path_absolutize::*;
+use unicode_normalization::UnicodeNormalization;
+
+/// Maps paths of stylesheets to the paths names are hashed from, so names do
+/// not depend on how a stylesheet is reached.
+#[derive(Debug)]
+pub struct HashPaths {
+    /// The directory hashed paths are relative to.
+    context: PathBuf,
+    /// Resolve symlinks before hashing.
+    canonicalize: bool,
+    /// Package names with their absolute directories.
+    package_roots: Vec<(PathBuf, PathBuf)>,
+}
+
+impl HashPaths {
+    /// Directories of `package_roots` are relative to the context.
+    pub fn new(
+        context: &Path,
+        canonicalize: bool,
+        package_roots: &BTreeMap<String, String>,
+    ) -> Self {
+        let resolve = |path: &Path| {
+            let path = path.absolutize().unwrap().to_path_buf();
+
+            if canonicalize {
+                fs::canonicalize(&path).unwrap_or(path)
+            } else {
+                path
+            }
+        };
+
+        Self {
+            context: resolve(context),
+            canonicalize,
+            package_roots: package_roots
+                .iter()
+                .map(|(name, dir)| (PathBuf::from(name), resolve(&context.join(dir))))
+                .collect(),
+        }
+    }
+
+    /// Returns the directory hashed paths are relative to.
+    pub fn context(&self) -> &Path {
+        &self.context
+    }
+
+    /// Returns the path the names of the stylesheet are hashed from.
+    ///
+    /// Stylesheets of packages are hashed as `<context>/<package>/<path in the package>`
+    /// whether they are reached through `node_modules` or the package directory.
+    pub fn hash_path(&self, filepath: &Path) -> PathBuf {
+        let filepath = if self.canonicalize {
+            fs::canonicalize(filepath).unwrap_or_else(|_| filepath.to_path_buf())
+        } else {
+            filepath.to_path_buf()
+        };
+
+        self.package_path(&filepath).unwrap_or(filepath)
+    }
+
+    fn package_path(&self, filepath: &Path) -> Option<PathBuf> {
+        // packages/ui/Button.module.css
+        for (name, root) in &self.package_roots {
+            if let Ok(rest) = filepath.strip_prefix(root) {
+                return Some(self.context.join(name).join(rest));
+            }
+        }
+
+        // node_modules/@scope/ui/Button.module.css
+        let components: Vec<_> = filepath.components().collect();
+
+        components
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, component)| **component == Component::Normal("node_modules".as_ref()))
+            .find_map(|(index, _)| {
+                let in_node_modules: PathBuf = components[index + 1..].iter().collect();
+
+                self.package_roots.iter().find_map(|(name, _)| {
+                    let rest = in_node_modules.strip_prefix(name).ok()?;
+
+                    Some(self.context.join(name).join(rest))
+                })
+            })
+    }
+}
+
+/// Returns the path of the file in the nearest package prefixed by the
+/// package name, `None` if the file is not in a named package.
+///
+/// `packages/ui/src/Button.module.css` → `@scope/ui/src/Button.module.css`
+pub fn package_relative(filepath: &Path) -> Option<String> {
+    filepath.ancestors().skip(1).find_map(|dir| {
+        let manifest = fs::read_to_string(dir.join("package.json")).ok()?;
+        let manifest: serde_json::Value = serde_json::from_str(&manifest).ok()?;
+        let name = manifest["name"].as_str()?;
+
+        Some(format!(
+            "{name}/{}",
+            relative(&filepath.to_string_lossy(), &dir.to_string_lossy())
+        ))
+    })
+}
+
+/// Normalizes the path, so paths passed by any entrypoint are byte-identical:
+///
+/// - separators are `/`
+/// - verbatim prefixes are removed: `\\?\C:\` → `C:/`, `\\?\UNC\server\share` → `//server/share`
+/// - drive letters are uppercase: `c:` → `C:`
+/// - `.` and `..` segments, repeated and trailing separators are removed
+/// - unicode is in NFC, macOS file systems may return NFD
+pub fn normalize(path: &str) -> String {
+    let path = path.nfc().collect::<String>().replace('\\', "/");
+
+    let path = if let Some(unc) = path.strip_prefix("//?/UNC/") {
+        format!("//{unc}")
+    } else if let Some(verbatim) = path.strip_prefix("//?/") {
+        verbatim.to_string()
+    } else {
+        path
+    };
+
+    let (root, rest) = split_root(&path);
+    let mut segments: Vec<&str> = vec![];
+
+    for segment in rest.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." if segments.last().is_some_and(|last| *last != "..") => {
+                segments.pop();
+            }
+            // ../ of the root is the root
+            ".." if !root.is_empty() => {}
+            _ => segments.push(segment),
+        }
+    }
+
+    match (root.as_str(), segments.is_empty()) {
+        ("", true) => ".".to_string(),
+        (unc, true) if unc.starts_with("//") => unc.trim_end_matches('/').to_string(),
+        (root, _) => format!("{root}{}", segments.join("/")),
+    }
+}
+
+/// Returns the normalized absolute path, relative paths are relative to the
+/// directory.
+///
+/// swc/cli passes the file name relative to cwd, swc-loader and jest pass
+/// absolute file names.
+pub fn absolute(path: &str, dir: &str) -> String {
+    let path = normalize(path);
+
+    if split_root(&path).0.is_empty() {
+        normalize(&format!("{dir}/{path}"))
+    } else {
+        path
+    }
+}
+
+/// Returns the normalized path relative to the context, the path names are
+/// hashed from.
+///
+/// Paths on different drives stay absolute like `path.relative` of Node.js
+/// returns them.
+pub fn relative(path: &str, context: &str) -> String {
+    let path = normalize(path);
+    let context = normalize(context);
+
+    let (path_root, path_rest) = split_root(&path);
+    let (context_root, context_rest) = split_root(&context);
+
+    if path_root != context_root {
+        return path;
+    }
+
+    let path_segments: Vec<_> = path_rest.split('/').filter(|s| !s.is_empty()).collect();
+    let context_segments: Vec<_> = context_rest.split('/').filter(|s| !s.is_empty()).collect();
+
+    let common = path_segments
+        .iter()
+        .zip(&context_segments)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut segments = vec![".."; context_segments.len() - common];
+    segments.extend(&path_segments[common..]);
+
+    segments.join("/")
+}
+
+/// Splits the path with `/` separators to the root and the rest. The root is
+/// `/`, `C:/`, `//server/share/` or empty for relative paths.
+fn split_root(path: &str) -> (String, &str) {
+    if let Some(unc) = path.strip_prefix("//") {
+        let mut parts = unc.splitn(3, '/');
+        let server = parts.next().unwrap_or_default();
+        let share = parts.next().unwrap_or_default();
+
+        return (
+            format!("//{server}/{share}/"),
+            parts.next().unwrap_or_default(),
+        );
+    }
+
+    match path.as_bytes() {
+        [drive, b':', ..] if drive.is_ascii_alphabetic() => (
+            format!("{}:/", drive.to_ascii_uppercase() as char),
+            &path[2..],
+        ),
+        [b'/', ..] => ("/".to_string(), &path[1..]),
+        _ => (String::new(), path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
+
+    use super::{absolute, normalize, relative, HashPaths};
+
+    #[test]
+    fn package_roots() {
+        let package_roots = BTreeMap::from([("@scope/ui".to_string(), "packages/ui".to_string())]);
+        let hash_paths = HashPaths::new(Path::new("/repo"), false, &package_roots);
+
+        let expected = PathBuf::from("/repo/@scope/ui/src/Button.module.css");
+
+        assert_eq!(
+            hash_paths.hash_path(Path::new("/repo/packages/ui/src/Button.module.css")),
+            expected
+        );
+        assert_eq!(
+            hash_paths.hash_path(Path::new(
+                "/repo/apps/web/node_modules/@scope/ui/src/Button.module.css"
+            )),
+            expected
+        );
+        assert_eq!(
+            hash_paths.hash_path(Path::new(
+                "/repo/node_modules/.pnpm/@scope+ui@1.0.0/node_modules/@scope/ui/src/Button.module.css"
+            )),
+            expected
+        );
+        assert_eq!(
+            hash_paths.hash_path(Path::new("/repo/apps/web/App.module.css")),
+            PathBuf::from("/repo/apps/web/App.module.css")
+        );
+    }
+
+    #[test]
+    fn normalized_paths() {
+        assert_eq!(
+            normalize("/repo/./src//a/../Button.module.css"),
+            "/repo/src/Button.module.css"
+        );
+        assert_eq!(normalize("/repo/src/"), "/repo/src");
+        assert_eq!(normalize("/../repo"), "/repo");
+        assert_eq!(normalize("./src/a.css"), "src/a.css");
+        assert_eq!(normalize("../a.css"), "../a.css");
+        assert_eq!(normalize("."), ".");
+        assert_eq!(normalize("c:\\Users\\dev\\"), "C:/Users/dev");
+        assert_eq!(normalize("\\\\?\\C:\\Users\\dev"), "C:/Users/dev");
+        assert_eq!(normalize("\\\\server\\share\\app\\"), "//server/share/app");
+        assert_eq!(
+            normalize("\\\\?\\UNC\\server\\share\\app"),
+            "//server/share/app"
+        );
+        assert_eq!(normalize("//server/share/"), "//server/share");
+        // e + combining acute accent
+        assert_eq!(
+            normalize("/repo/Cafe\u{301}.module.css"),
+            "/repo/Caf\u{e9}.module.css"
+        );
+    }
+
+    #[test]
+    fn entrypoint_paths() {
+        let expected = "/repo/src/Button.module.css";
+
+        // swc-loader and jest
+        assert_eq!(absolute("/repo/src/Button.module.css", "/repo"), expected);
+        // swc/cli
+        assert_eq!(absolute("src/Button.module.css", "/repo"), expected);
+        assert_eq!(absolute("./src/Button.module.css", "/repo/"), expected);
+
+        assert_eq!(
+            absolute("src\\Button.module.css", "c:\\repo"),
+            "C:/repo/src/Button.module.css"
+        );
+        assert_eq!(
+            absolute("D:\\src\\Button.module.css", "C:\\repo"),
+            "D:/src/Button.module.css"
+        );
+    }
+
+    #[test]
+    fn relative_paths() {
+        assert_eq!(relative("/repo/src/a.css", "/repo/"), "src/a.css");
+        assert_eq!(
+            relative("/repo/node_modules/ui/a.css", "/repo/src"),
+            "../node_modules/ui/a.css"
+        );
+        assert_eq!(
+            relative("/repository/a.css", "/repo"),
+            "../repository/a.css"
+        );
+        assert_eq!(relative("c:\\repo\\src\\a.css", "C:\\repo"), "src/a.css");
+        assert_eq!(
+            relative("\\\\?\\C:\\repo\\src\\a.css", "C:\\repo"),
+            "src/a.css"
+        );
+        assert_eq!(relative("D:\\src\\a.css", "C:\\repo"), "D:/src/a.css");
+        assert_eq!(
+            relative("\\\\server\\share\\app\\a.css", "\\\\server\\share\\app"),
+            "a.css"
+        );
+    }
+}
@@ -1,77 +1,336 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use path_absolutize::*;
 use swc_core::atoms::Wtf8Atom;
-use swc_core::ecma::ast::{Expr, ImportDecl, ImportSpecifier, Lit, MemberProp};
+use swc_core::common::{Span, Spanned, DUMMY_SP};
+use swc_core::ecma::ast::{
+    ArrowExpr, ArrowFunctionBody, BinExpr, BinaryOp, Bool, CallExpr, Callee, Decl, ExportAll,
+    ExportDefaultExpr, ExportNamedSpecifier, ExportSpecifier, Expr, ExprOrSpread, Id, Ident,
+    IdentName, ImportDecl, ImportSpecifier, KeyValueProp, Lit, MemberExpr, MemberProp, ModuleDecl,
+    ModuleExportName, ModuleItem, NamedExport, ObjectLit, OptChainBase, OptChainExpr, ParenExpr,
+    Pat, Program, Prop, PropName, PropOrSpread, Stmt, Str, Tpl, VarDecl, VarDeclKind,
+    VarDeclarator,
+};
 use swc_core::ecma::atoms::Atom;
-use swc_core::ecma::visit::{VisitMut, VisitMutWith};
-use swc_core::plugin::errors::HANDLER;
+use swc_core::ecma::visit::{Visit, VisitMut, VisitMutWith, VisitWith};
 
+use crate::declarations;
+use crate::diagnostics::{self, Kind};
 use crate::generic_names::{Generator, Options};
-use crate::Config;
+use crate::hash_path::{self, HashPaths};
+use crate::identifier::{is_identifier, is_reserved_word};
+use crate::loader_utils::hash::get_hash_digest;
+use crate::overrides::Overrides;
+use crate::resolver::Resolver;
+use crate::stylesheet::{self, ComposesFrom, Scope, Stylesheet, Stylesheets, SymbolKind, Value};
+use crate::usage::Usage;
+use crate::{Config, HashInput, LocalsConvention, Severity};
+
+/// A helper bound to a stylesheet like `const cx = classNames.bind(styles)`.
+#[derive(Debug)]
+struct Bound {
+    filepath: PathBuf,
+    /// The unbound helper like `classNames`.
+    helper: Ident,
+    /// The binding of the stylesheet import.
+    styles: Id,
+    /// Calls with all arguments replaced by generated names.
+    rewritten: usize,
+}
+
+/// Counts references of every binding.
+#[derive(Default)]
+struct References(HashMap<Id, usize>);
+
+impl Visit for References {
+    fn visit_ident(&mut self, ident: &Ident) {
+        *self.0.entry(ident.to_id()).or_default() += 1;
+    }
+}
+
+/// Replaces helpers bound to stylesheets with the unbound ones.
+struct Unbinder(HashMap<Id, Ident>);
+
+impl VisitMut for Unbinder {
+    fn visit_mut_var_declarator(&mut self, declarator: &mut VarDeclarator) {
+        declarator.visit_mut_children_with(self);
+
+        if let (Pat::Ident(binding), Some(init)) = (&declarator.name, &mut declarator.init) {
+            if let Some(helper) = self.0.get(&binding.id.to_id()) {
+                **init = Expr::Ident(helper.clone());
+            }
+        }
+    }
+}
+
+/// Generators of scoped names with the same `hash_prefix`.
+struct Generators {
+    generator: Generator,
+    /// Generators for symbols with their own patterns.
+    kinds: HashMap<SymbolKind, Generator>,
+}
+
+impl Generators {
+    /// `pattern` is used for symbols without their own patterns.
+    fn new(config: &Config, pattern: &str, hash_prefix: &str, context: &Path) -> Self {
+        let new_generator = |pattern: &str| {
+            Generator::new_with_options(
+                pattern,
+                Options {
+                    context: context.to_path_buf(),
+                    hash_prefix: hash_prefix.to_string(),
+                },
+            )
+        };
+
+        let kinds = [
+            (SymbolKind::Keyframes, &config.keyframes_scoped_name),
+            (
+                SymbolKind::CustomProperty,
+                &config.custom_property_scoped_name,
+            ),
+            (SymbolKind::Grid, &config.grid_scoped_name),
+            (SymbolKind::Container, &config.container_scoped_name),
+        ]
+        .into_iter()
+        .filter_map(|(kind, pattern)| Some((kind, new_generator(pattern.as_deref()?))))
+        .collect();
+
+        Self {
+            generator: new_generator(pattern),
+            kinds,
+        }
+    }
+
+    fn get(&self, kind: SymbolKind) -> &Generator {
+        self.kinds.get(&kind).unwrap_or(&self.generator)
+    }
+}
 
 /// Returns the full path to the file's directory.
 ///
 /// - swc/loader and swc/jest pass full `filepath`
-/// - swc/cli pass relative `filepath`
-fn get_dir(mut context: PathBuf, filepath: PathBuf) -> PathBuf {
-    if filepath.has_root() {
-        return filepath.parent().unwrap().to_path_buf();
-    }
+/// - swc/cli pass `filepath` relative to `cwd`
+fn get_dir(cwd: &str, filepath: &str) -> PathBuf {
+    let filepath = hash_path::absolute(filepath, cwd);
 
-    context.push(filepath);
-
-    context.parent().unwrap().to_path_buf()
+    Path::new(&filepath).parent().unwrap().to_path_buf()
 }
 
 pub struct Injector {
     dir: PathBuf,
+    /// The directory paths to stylesheets are relative to.
+    context: PathBuf,
     config: Config,
+    resolver: Resolver,
+    hash_paths: HashPaths,
 
-    generator: Generator,
-    imports: HashMap<Atom, PathBuf>,
-    named_imports: HashMap<Atom, (Atom, PathBuf)>,
+    generators: Generators,
+    /// Convention of names exported to JS.
+    convention: LocalsConvention,
+    imports: HashMap<Id, PathBuf>,
+    named_imports: HashMap<Id, (Atom, PathBuf)>,
+    stylesheets: Stylesheets,
+    /// Names in stylesheets by names exported to JS.
+    locals: HashMap<PathBuf, HashMap<String, String>>,
+    /// Stylesheets with written TypeScript declarations.
+    declared: HashSet<PathBuf>,
+    /// Stylesheets with an emitted import for re-exports.
+    reexported: HashSet<PathBuf>,
+    /// Locals accessed by the file.
+    usage: Usage,
+    /// Imported bound class helpers like `classNames` of `classnames/bind`.
+    binders: HashSet<Id>,
+    /// Helpers bound to stylesheets like `const cx = classNames.bind(styles)`.
+    bound: HashMap<Id, Bound>,
+    /// Numbers of references of bindings in the program before it is
+    /// transformed.
+    references: HashMap<Id, usize>,
+    /// Imported class joining helpers like `clsx`.
+    joiners: HashSet<Id>,
+    /// Bindings of stylesheet imports with usages which cannot be inlined.
+    escaped: HashSet<Id>,
+    /// Stylesheets and names of generated names to report collisions.
+    generated: HashMap<String, (PathBuf, String)>,
+    /// What names of stylesheets are hashed from instead of their paths.
+    hash_inputs: HashMap<PathBuf, Option<String>>,
+    overrides: Overrides,
+    /// Generators of overrides matching stylesheets.
+    override_generators: HashMap<PathBuf, Option<Generators>>,
 }
 
 impl Injector {
     pub fn new(cwd: &str, filepath: &str, config: Config) -> Self {
-        let context = PathBuf::from(if config.root.is_empty() {
+        // A relative cwd is relative to the current directory of the process
+        let cwd = &if Path::new(cwd).is_relative() {
+            Path::new(cwd).absolutize().map_or_else(
+                |_| cwd.to_string(),
+                |cwd| cwd.to_string_lossy().into_owned(),
+            )
+        } else {
             cwd.to_string()
+        };
+
+        let context = PathBuf::from(if config.root.is_empty() {
+            hash_path::normalize(cwd)
         } else {
-            config.root.clone()
+            hash_path::absolute(&config.root, cwd)
         });
 
-        let dir = get_dir(context.clone(), PathBuf::from(filepath));
+        let dir = get_dir(cwd, filepath);
+
+        // Overrides are resolved once for the file
+        let file = hash_path::relative(
+            &hash_path::absolute(filepath, cwd),
+            &context.to_string_lossy(),
+        );
+        let overrides = Overrides::new(&config);
+        let config = overrides.for_file(&config, &file);
+
+        let hash_paths = HashPaths::new(&context, config.canonicalize, &config.package_roots);
+
+        let generators = Generators::new(
+            &config,
+            &config.generate_scoped_name,
+            &config.hash_prefix,
+            hash_paths.context(),
+        );
 
         Self {
             dir,
+            context: context.clone(),
             config: config.clone(),
+            resolver: Resolver::new(&context, &config),
+            hash_paths,
             imports: HashMap::new(),
             named_imports: HashMap::new(),
-            generator: Generator::new_with_options(
-                config.generate_scoped_name.as_str(),
-                Options {
-                    context,
-                    hash_prefix: config.hash_prefix,
-                },
-            ),
+            stylesheets: Stylesheets::default(),
+            locals: HashMap::new(),
+            declared: HashSet::new(),
+            reexported: HashSet::new(),
+            usage: Usage::default(),
+            binders: HashSet::new(),
+            bound: HashMap::new(),
+            references: HashMap::new(),
+            joiners: HashSet::new(),
+            escaped: HashSet::new(),
+            generated: HashMap::new(),
+            hash_inputs: HashMap::new(),
+            overrides,
+            override_generators: HashMap::new(),
+            generators,
+            convention: if config.named_export {
+                config.locals_convention.named_export()
+            } else {
+                config.locals_convention
+            },
+        }
+    }
+
+    /// Returns the name inlined instead of `styles[key]` for the stylesheet.
+    pub fn name(&mut self, stylesheet: &Path, key: &str) -> String {
+        self.resolve(key, stylesheet.to_path_buf(), DUMMY_SP)
+    }
+
+    /// Returns all names of the stylesheet with inlined values.
+    ///
+    /// Returns `None` if the stylesheet cannot be read.
+    pub fn manifest(&mut self, stylesheet: &Path) -> Option<BTreeMap<String, String>> {
+        self.stylesheets.get(stylesheet)?;
+
+        let manifest = self
+            .exported_names(stylesheet, DUMMY_SP)?
+            .into_iter()
+            .map(|key| {
+                let name = self.name(stylesheet, &key);
+
+                (key, name)
+            })
+            .collect();
+
+        Some(manifest)
+    }
+
+    /// Returns the stylesheet with scoped names like css-loader with the same
+    /// options outputs it.
+    pub fn rewrite(&mut self, filepath: &Path, source: &str) -> String {
+        let parsed = self.stylesheets.insert(filepath, Stylesheet::parse(source));
+
+        stylesheet::rewrite(source, filepath, &parsed, self)
+    }
+
+    /// Returns classes of the stylesheet not accessed according to the usage.
+    ///
+    /// Returns `None` if the stylesheet cannot be read.
+    pub fn unused(&mut self, usage: &Usage, stylesheet: &Path) -> Option<Vec<String>> {
+        let path = self.relative_path(stylesheet);
+
+        let classes: BTreeSet<_> = self
+            .stylesheets
+            .get(stylesheet)?
+            .symbols
+            .iter()
+            .filter(|(name, kind)| **kind == SymbolKind::Class && !usage.is_used(&path, name))
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        Some(classes.into_iter().collect())
+    }
+
+    /// Returns the path of the stylesheet relative to the root like in the
+    /// usage file.
+    fn relative_path(&self, stylesheet: &Path) -> String {
+        hash_path::relative(
+            &stylesheet.to_string_lossy(),
+            &self.context.to_string_lossy(),
+        )
+    }
+
+    /// Records the local of the stylesheet as accessed by the file.
+    fn record_usage(&mut self, filepath: &Path, local: &str) {
+        if self.config.usage_file.is_some() {
+            let stylesheet = self.relative_path(filepath);
+
+            self.usage.add(&stylesheet, local);
+        }
+    }
+
+    /// Appends locals accessed by the file to the usage file.
+    fn flush_usage(&mut self) {
+        let Some(usage_file) = &self.config.usage_file else {
+            return;
+        };
+
+        if self.usage.is_empty() {
+            return;
+        }
+
+        let path = self.context.join(usage_file);
+
+        if let Err(error) = self.usage.append_to(&path) {
+            diagnostics::report(
+                &self.config,
+                Kind::FileAccess,
+                DUMMY_SP,
+                &format!("Cannot write {}: {}", path.to_string_lossy(), error),
+            );
         }
+
+        self.usage = Usage::default();
     }
 
     fn filepath_from_src(&self, src: &Wtf8Atom) -> PathBuf {
-        let path_buf = PathBuf::from(src.as_atom().expect("non-utf8 string").to_string());
+        let specifier = src.as_str().expect("non-utf8 string");
 
-        let filepath = path_buf
-            .absolutize_from(self.dir.clone())
-            .unwrap()
-            .to_path_buf();
+        let filepath = self.resolver.resolve(specifier, &self.dir);
 
         if !filepath.has_root() {
             panic!(
                 "dir: {}; p: {}; filepath: {}",
                 self.dir.to_str().unwrap(),
-                path_buf.to_str().unwrap(),
+                specifier,
                 filepath.to_str().unwrap()
             )
         }
@@ -79,149 +338,1656 @@ impl Injector {
         filepath
     }
 
-    fn new_import(&mut self, local: &Atom, src: &Wtf8Atom) {
+    fn new_import(&mut self, local: &Ident, src: &Wtf8Atom) {
         let filepath = self.filepath_from_src(src);
 
-        self.imports.insert(local.clone(), filepath);
+        self.declare(&filepath);
+
+        self.imports.insert(local.to_id(), filepath);
     }
 
-    fn new_named_import(&mut self, imported: &Atom, local: &Atom, src: &Wtf8Atom) {
+    fn new_named_import(&mut self, imported: &Atom, local: &Ident, src: &Wtf8Atom) {
         let filepath = self.filepath_from_src(src);
 
+        self.declare(&filepath);
+
         self.named_imports
-            .insert(local.clone(), (imported.clone(), filepath));
+            .insert(local.to_id(), (imported.clone(), filepath));
     }
 
-    /// Returns class name from list.
-    fn generated_name(&self, module: &Atom, name: &Atom) -> String {
-        let filepath = self.imports.get(module).unwrap().to_path_buf();
+    fn is_css_module(&self, src: &Wtf8Atom) -> bool {
+        src.as_str()
+            .expect("non-utf8 string")
+            .ends_with(self.config.css_modules_suffix.as_str())
+    }
 
-        self.generator.generate(name.to_string().as_str(), filepath)
+    /// Returns names under which the name of the stylesheet is exported to JS.
+    fn keys(&self, name: &str) -> Vec<String> {
+        let mut keys = self.convention.keys(name);
+
+        // .class → _class
+        if self.config.named_export {
+            for key in keys.iter_mut().filter(|key| is_reserved_word(key)) {
+                key.insert_str(0, &self.config.named_export_prefix);
+            }
+        }
+
+        keys
     }
 
-    fn generated_name_for_named_import(&self, name: &Atom) -> String {
-        let (imported, filepath) = self.named_imports.get(name).unwrap();
+    /// Returns the name in the stylesheet for the name used in JS.
+    fn local_name(&mut self, key: &str, filepath: &Path) -> String {
+        if let Some(name) = self.stylesheet_name(filepath, key) {
+            return name;
+        }
 
-        self.generator
-            .generate(imported.to_string().as_str(), filepath.to_path_buf())
+        // The stylesheet cannot be read
+        if self.config.named_export {
+            key.strip_prefix(self.config.named_export_prefix.as_str())
+                .filter(|name| is_reserved_word(name))
+                .unwrap_or(key)
+                .to_string()
+        } else {
+            key.to_string()
+        }
     }
-}
 
-impl VisitMut for Injector {
-    fn visit_mut_expr(&mut self, expression: &mut Expr) {
-        expression.visit_mut_children_with(self);
+    /// Returns the name in the stylesheet for the name used in JS, `None` if
+    /// the stylesheet does not export it or cannot be read.
+    fn stylesheet_name(&mut self, filepath: &Path, key: &str) -> Option<String> {
+        if !self.locals.contains_key(filepath) {
+            let stylesheet = self.stylesheets.get(filepath)?;
+            let mut locals = HashMap::new();
 
-        if self.imports.is_empty() && self.named_imports.is_empty() {
+            for name in stylesheet.names() {
+                for key in self.keys(name) {
+                    locals.entry(key).or_insert_with(|| name.to_string());
+                }
+            }
+
+            self.locals.insert(filepath.to_path_buf(), locals);
+        }
+
+        self.locals[filepath].get(key).cloned()
+    }
+
+    /// Returns `:export` or `@value` value if the stylesheet declares it,
+    /// otherwise generated class name.
+    fn resolve(&mut self, key: &str, filepath: PathBuf, span: Span) -> String {
+        let name = self.local_name(key, &filepath);
+
+        self.record_usage(&filepath, &name);
+
+        if let Some(stylesheet) = self.stylesheets.get(&filepath) {
+            let file_name = filepath.file_name().unwrap_or_default().to_string_lossy();
+
+            // .root :global(.is-open)
+            if stylesheet.is_global_only(&name) {
+                diagnostics::report(
+                    &self.config,
+                    Kind::GlobalClass,
+                    span,
+                    &format!(
+                        "\"{}\" is declared only in :global context of {} and is not exported, use the \"{}\" string instead",
+                        key, file_name, name
+                    ),
+                );
+            } else if self.stylesheet_name(&filepath, key).is_none() {
+                diagnostics::report(
+                    &self.config,
+                    Kind::UnknownClass,
+                    span,
+                    &format!("\"{}\" is not exported by {}", key, file_name),
+                );
+            }
+        }
+
+        match self.stylesheets.constant(&filepath, &name, &|source, dir| {
+            self.resolver.resolve(source, dir)
+        }) {
+            Ok(Some(value)) => return value,
+            Ok(None) => {}
+            Err(error) => {
+                diagnostics::report(&self.config, Kind::CyclicValue, span, &error.to_string())
+            }
+        }
+
+        let generated = self.generate(&name, filepath.clone());
+
+        self.check_collision(&generated, &name, filepath.clone(), span);
+
+        let mut names = vec![generated];
+        self.compose(&name, &filepath, &mut names, &mut HashSet::new());
+
+        names.join(" ")
+    }
+
+    /// Adds names of classes the class composes like css-loader exports them.
+    ///
+    /// ```css
+    /// .a { composes: b; composes: c from "./other.css"; }
+    /// ```
+    ///
+    /// `a_hash` → `a_hash b_hash c_hash`
+    fn compose(
+        &mut self,
+        name: &str,
+        filepath: &Path,
+        names: &mut Vec<String>,
+        visited: &mut HashSet<(PathBuf, String)>,
+    ) {
+        if !visited.insert((filepath.to_path_buf(), name.to_string())) {
             return;
         }
 
-        match expression {
-            Expr::Member(member) => {
-                if let Expr::Ident(obj) = &*member.obj {
-                    // Check variable usage for css modules
-                    if !self.imports.contains_key(&obj.sym) {
-                        return;
+        let Some(stylesheet) = self.stylesheets.get(filepath) else {
+            return;
+        };
+
+        for composes in stylesheet.composes.get(name).into_iter().flatten() {
+            let source = match &composes.from {
+                ComposesFrom::Local => Some(filepath.to_path_buf()),
+                ComposesFrom::Global => None,
+                ComposesFrom::File(from) => {
+                    let specifier = match stylesheet.values.get(from) {
+                        // @value other: "./other.css";
+                        // composes: a from other;
+                        Some(Value::Literal(source)) => stylesheet::unquote(source),
+                        _ => stylesheet::unquote(from),
+                    };
+                    let dir = filepath.parent().unwrap_or(Path::new(""));
+
+                    Some(self.resolver.resolve(specifier, dir))
+                }
+            };
+
+            for composed in &composes.names {
+                let Some(source) = &source else {
+                    // composes: a from global;
+                    if !names.contains(composed) {
+                        names.push(composed.clone());
                     }
 
-                    match &member.prop {
-                        // styles.title
-                        MemberProp::Ident(i) => {
-                            let generated_name = self.generated_name(&obj.sym, &i.sym);
+                    continue;
+                };
 
-                            let exp = Expr::from(generated_name);
+                // A class used only through composes is still used
+                self.record_usage(source, composed);
 
-                            expression.clone_from(&exp)
-                        }
+                let generated = self.generate(composed, source.clone());
 
-                        MemberProp::Computed(computed) => match &*computed.expr {
-                            // styles['Component--disabled']
-                            Expr::Lit(Lit::Str(str_lit)) => {
-                                let generated_name = self.generated_name(
-                                    &obj.sym,
-                                    str_lit.value.as_atom().expect("non-utf8 key"),
-                                );
-
-                                let exp = Expr::from(generated_name);
-
-                                expression.clone_from(&exp)
-                            }
-
-                            // styles[prefix + "title"]
-                            _ => HANDLER.with(|handler| {
-                                handler
-                                    .struct_span_err(
-                                        computed.span,
-                                        "Computed hit cannot be injected",
-                                    )
-                                    .emit()
-                            }),
-                        },
-
-                        _ => {}
-                    }
+                if !names.contains(&generated) {
+                    names.push(generated);
                 }
+
+                self.compose(composed, source, names, visited);
             }
+        }
+    }
 
-            // import { foo } from "./Component.module.css"
-            //
-            // className(foo)
-            Expr::Ident(ident) => {
-                if !self.named_imports.contains_key(&ident.sym) {
-                    return;
-                }
+    /// Reports the generated name if it was generated for another name.
+    fn check_collision(&mut self, generated: &str, name: &str, filepath: PathBuf, span: Span) {
+        if Kind::Collision.severity(&self.config) == Severity::Off {
+            return;
+        }
+
+        let source = (filepath, name.to_string());
+
+        let Some(other) = self.generated.get(generated) else {
+            self.generated.insert(generated.to_string(), source);
 
-                let generated_name = self.generated_name_for_named_import(&ident.sym);
+            return;
+        };
+
+        if *other != source {
+            let message = format!(
+                "\"{}\" is generated for both \"{}\" of {} and \"{}\" of {}",
+                generated,
+                other.1,
+                self.relative_path(&other.0),
+                source.1,
+                self.relative_path(&source.0)
+            );
+
+            diagnostics::report(&self.config, Kind::Collision, span, &message);
+        }
+    }
 
-                let exp = Expr::from(generated_name);
+    /// Returns the kind of the name declared by the stylesheet.
+    ///
+    /// Names are classes unless the stylesheet says otherwise.
+    fn symbol_kind(&mut self, name: &str, filepath: &Path) -> SymbolKind {
+        let kind = self
+            .stylesheets
+            .get(filepath)
+            .and_then(|stylesheet| stylesheet.symbols.get(name).copied());
 
-                expression.clone_from(&exp)
+        match kind {
+            Some(SymbolKind::CustomProperty) | None
+                if self.config.dashed_idents && name.starts_with("--") =>
+            {
+                SymbolKind::CustomProperty
             }
-            _ => {}
+            Some(SymbolKind::CustomProperty) | None => SymbolKind::Class,
+            Some(kind) => kind,
         }
     }
 
-    fn visit_mut_import_decl(&mut self, n: &mut ImportDecl) {
-        n.visit_mut_children_with(self);
+    /// Generates the scoped name using the pattern for the kind of the name.
+    fn generate(&mut self, name: &str, filepath: PathBuf) -> String {
+        let kind = self.symbol_kind(name, &filepath);
+        let input = self.hash_input(&filepath);
+        self.cache_override_generator(&filepath);
 
-        // Check if it's a css modules import
+        let generator = self
+            .override_generators
+            .get(&filepath)
+            .and_then(Option::as_ref)
+            .unwrap_or(&self.generators)
+            .get(kind);
+        let filepath = self.hash_paths.hash_path(&filepath);
 
-        if !n
-            .src
-            .value
-            .as_str()
-            .expect("non-utf8 string")
-            .ends_with(self.config.css_modules_suffix.as_str())
-            || n.specifiers.is_empty()
+        let generate = |name: &str| match &input {
+            Some(input) => generator.generate_from(name, filepath.clone(), input),
+            None => generator.generate(name, filepath.clone()),
+        };
+
+        match kind {
+            // --brand → --[hash]
+            SymbolKind::CustomProperty => format!("--{}", generate(&name[2..])),
+            _ => generate(name),
+        }
+    }
+
+    /// Creates generators with `generate_scoped_name` and `hash_prefix` of
+    /// overrides matching the stylesheet.
+    fn cache_override_generator(&mut self, filepath: &Path) {
+        if self.override_generators.contains_key(filepath) {
+            return;
+        }
+
+        let stylesheet = self.relative_path(filepath);
+
+        let generators =
+            self.overrides
+                .naming(&self.config, &stylesheet)
+                .map(|(pattern, hash_prefix)| {
+                    Generators::new(
+                        &self.config,
+                        &pattern,
+                        &hash_prefix,
+                        self.hash_paths.context(),
+                    )
+                });
+
+        self.override_generators
+            .insert(filepath.to_path_buf(), generators);
+    }
+
+    /// Returns what names of the stylesheet are hashed from instead of its
+    /// path, read once for every stylesheet.
+    ///
+    /// Returns `None` for `hash_input: "path"` or if the input cannot be read.
+    fn hash_input(&mut self, filepath: &Path) -> Option<String> {
+        if self.config.hash_input == HashInput::Path {
+            return None;
+        }
+
+        if let Some(input) = self.hash_inputs.get(filepath) {
+            return input.clone();
+        }
+
+        let input = match self.config.hash_input {
+            HashInput::Content => match fs::read(filepath) {
+                Ok(content) => Some(get_hash_digest(&content, "xxhash64", "hex", 16)),
+                Err(error) => {
+                    diagnostics::report(
+                        &self.config,
+                        Kind::HashInput,
+                        DUMMY_SP,
+                        &format!(
+                            "Cannot read {} to hash its content, the path is hashed instead: {}",
+                            filepath.to_string_lossy(),
+                            error
+                        ),
+                    );
+
+                    None
+                }
+            },
+            HashInput::Package => {
+                let input = hash_path::package_relative(filepath);
+
+                if input.is_none() {
+                    diagnostics::report(
+                        &self.config,
+                        Kind::HashInput,
+                        DUMMY_SP,
+                        &format!(
+                            "{} is not in a package with a name, the path is hashed instead",
+                            filepath.to_string_lossy()
+                        ),
+                    );
+                }
+
+                input
+            }
+            HashInput::Path => None,
+        };
+
+        self.hash_inputs
+            .insert(filepath.to_path_buf(), input.clone());
+
+        input
+    }
+
+    /// Returns all names exported by the stylesheet.
+    ///
+    /// Emits an error if the stylesheet cannot be read.
+    fn exported_names(&mut self, filepath: &Path, span: Span) -> Option<Vec<String>> {
+        let Some(stylesheet) = self.stylesheets.get(filepath) else {
+            diagnostics::report(
+                &self.config,
+                Kind::UnreadableStylesheet,
+                span,
+                &format!(
+                    "Cannot read {} to get all its names",
+                    filepath.to_string_lossy()
+                ),
+            );
+
+            return None;
+        };
+
+        let names: BTreeSet<String> = stylesheet
+            .names()
+            .into_iter()
+            .filter(|name| {
+                self.config.dashed_idents
+                    || stylesheet.symbols.get(*name) != Some(&SymbolKind::CustomProperty)
+            })
+            .flat_map(|name| self.keys(name))
+            .collect();
+
+        Some(names.into_iter().collect())
+    }
+
+    /// Writes TypeScript declaration for the stylesheet once.
+    fn declare(&mut self, filepath: &Path) {
+        if !self.config.emit_declarations
+            || !self.declared.insert(filepath.to_path_buf())
+            || self.stylesheets.get(filepath).is_none()
         {
             return;
         }
 
-        let src = &n.src.value;
+        let Some(names) = self.exported_names(filepath, DUMMY_SP) else {
+            return;
+        };
 
-        // Extract variable name and process css modules
-        n.specifiers.iter().for_each(|specifier| match specifier {
-            // import { foo as bar } from "./Component.module.css"
-            ImportSpecifier::Named(named) => self.new_named_import(
-                &named
-                    .imported
-                    .clone()
-                    .map_or(named.local.sym.clone(), |s| s.atom().as_ref().to_owned()),
-                &named.local.sym,
+        if let Err(error) = declarations::write(
+            filepath,
+            &declarations::render(&names, self.config.named_export),
+        ) {
+            diagnostics::report(
+                &self.config,
+                Kind::FileAccess,
+                DUMMY_SP,
+                &format!(
+                    "Cannot write {}: {}",
+                    declarations::declaration_path(filepath).to_string_lossy(),
+                    error
+                ),
+            );
+        }
+    }
+
+    /// Returns an object with all names of the stylesheet like css-loader
+    /// creates in runtime.
+    fn materialize(&mut self, filepath: &Path, span: Span) -> Option<Expr> {
+        let entries = self
+            .exported_names(filepath, span)?
+            .into_iter()
+            .map(|name| {
+                let value = Expr::from(self.resolve(&name, filepath.to_path_buf(), span));
+
+                (name, value)
+            })
+            .collect();
+
+        Some(object_lit(entries))
+    }
+
+    /// Rewrites dynamic import of css module to import of the stylesheet and
+    /// the module namespace with generated names.
+    ///
+    /// ```js
+    /// import("./Component.module.css");
+    /// // ↓ ↓ ↓ ↓ ↓ ↓
+    /// import("./Component.module.css").then(() => ({
+    ///   default: { title: "title" },
+    ///   title: "title",
+    /// }));
+    /// ```
+    fn dynamic_import(&mut self, call: &CallExpr) -> Option<Expr> {
+        let Some(ExprOrSpread { spread: None, expr }) = call.args.first() else {
+            return None;
+        };
+
+        let Expr::Lit(Lit::Str(src)) = &**expr else {
+            return None;
+        };
+
+        if !self.is_css_module(&src.value) {
+            return None;
+        }
+
+        let filepath = self.filepath_from_src(&src.value);
+
+        self.declare(&filepath);
+
+        if self.stylesheets.get(&filepath).is_none() {
+            diagnostics::report(
+                &self.config,
+                Kind::UnreadableStylesheet,
+                call.span,
+                &format!(
+                    "Cannot read {} to inline dynamic import",
+                    filepath.to_string_lossy()
+                ),
+            );
+
+            return None;
+        }
+
+        let mut entries = vec![(
+            "default".to_string(),
+            self.materialize(&filepath, call.span)?,
+        )];
+
+        for name in self.exported_names(&filepath, call.span)? {
+            if is_identifier(&name) {
+                let value = Expr::from(self.resolve(&name, filepath.clone(), call.span));
+
+                entries.push((name, value));
+            }
+        }
+
+        let namespace = ArrowExpr {
+            span: DUMMY_SP,
+            ctxt: Default::default(),
+            params: vec![],
+            body: Box::new(ArrowFunctionBody::Expr(Box::new(Expr::Paren(ParenExpr {
+                span: DUMMY_SP,
+                expr: Box::new(object_lit(entries)),
+            })))),
+            is_async: false,
+            is_generator: false,
+            type_params: None,
+            return_type: None,
+        };
+
+        Some(Expr::Call(CallExpr {
+            span: call.span,
+            ctxt: Default::default(),
+            callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::new(Expr::Call(call.clone())),
+                prop: MemberProp::Ident(IdentName::new("then".into(), DUMMY_SP)),
+            }))),
+            args: vec![ExprOrSpread {
+                spread: None,
+                expr: Box::new(Expr::Arrow(namespace)),
+            }],
+            type_args: None,
+        }))
+    }
+
+    /// Rewrites re-exports of css modules to exports of generated names.
+    ///
+    /// ```js
+    /// export { title, default as styles } from "./Component.module.css";
+    /// // ↓ ↓ ↓ ↓ ↓ ↓
+    /// import "./Component.module.css";
+    /// export const title = "title";
+    /// export const styles = { title: "title" };
+    /// ```
+    fn reexport(&mut self, item: &ModuleItem) -> Option<Vec<ModuleItem>> {
+        let ModuleItem::ModuleDecl(decl) = item else {
+            return None;
+        };
+
+        let (src, specifiers) = match decl {
+            ModuleDecl::ExportNamed(NamedExport {
+                src: Some(src),
+                specifiers,
+                type_only: false,
+                ..
+            }) => (src, Some(specifiers)),
+
+            ModuleDecl::ExportAll(ExportAll {
                 src,
-            ),
+                type_only: false,
+                ..
+            }) => (src, None),
 
-            // import styles from "./Component.module.css"
-            ImportSpecifier::Default(default) => self.new_import(&default.local.sym, src),
+            _ => return None,
+        };
 
-            // import * as styles from "./Component.module.css"
-            ImportSpecifier::Namespace(namespace) => self.new_import(&namespace.local.sym, src),
-            #[cfg(swc_ast_unknown)]
-            _ => {}
-        });
+        if !self.is_css_module(&src.value) {
+            return None;
+        }
+
+        let filepath = self.filepath_from_src(&src.value);
+        let span = decl.span();
+
+        self.declare(&filepath);
+
+        let mut items = vec![];
+
+        if self.reexported.insert(filepath.clone()) {
+            items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                span,
+                specifiers: vec![],
+                src: src.clone(),
+                type_only: false,
+                with: None,
+                phase: Default::default(),
+            })));
+        }
+
+        let Some(specifiers) = specifiers else {
+            // export * from "./Component.module.css"
+            for name in self.exported_names(&filepath, span)? {
+                if is_identifier(&name) {
+                    let value = Expr::from(self.resolve(&name, filepath.clone(), span));
+
+                    items.extend(self.export_value(name.into(), value));
+                }
+            }
+
+            return Some(items);
+        };
+
+        for specifier in specifiers {
+            let (exported, value) = match specifier {
+                ExportSpecifier::Named(named) if named.is_type_only => continue,
+
+                // export { title as header } from "./Component.module.css"
+                // export { default as styles } from "./Component.module.css"
+                ExportSpecifier::Named(named) => {
+                    let orig = module_export_name(&named.orig);
+                    let exported = named
+                        .exported
+                        .as_ref()
+                        .map_or(orig.clone(), module_export_name);
+
+                    let value = if orig == "default" {
+                        self.materialize(&filepath, named.span)?
+                    } else {
+                        Expr::from(self.resolve(&orig, filepath.clone(), named.span))
+                    };
+
+                    (exported, value)
+                }
+
+                // export * as styles from "./Component.module.css"
+                ExportSpecifier::Namespace(namespace) => (
+                    module_export_name(&namespace.name),
+                    self.materialize(&filepath, namespace.span)?,
+                ),
+
+                // export styles from "./Component.module.css"
+                ExportSpecifier::Default(default) => (
+                    default.exported.sym.clone(),
+                    self.materialize(&filepath, default.exported.span)?,
+                ),
+
+                #[cfg(swc_ast_unknown)]
+                _ => return None,
+            };
+
+            items.extend(self.export_value(exported, value));
+        }
+
+        Some(items)
+    }
+
+    /// Returns items exporting the value by the name.
+    fn export_value(&self, exported: Atom, value: Expr) -> Vec<ModuleItem> {
+        // export default value
+        if exported == "default" {
+            return vec![ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(
+                ExportDefaultExpr {
+                    span: DUMMY_SP,
+                    expr: Box::new(value),
+                },
+            ))];
+        }
+
+        // The local is private, so it does not clash with bindings of the module
+        // const _name = value;
+        // export { _name as name }
+        let sym = format!("_{exported}");
+        let local = Ident::new_private(
+            if is_identifier(&sym) {
+                sym.into()
+            } else {
+                "_export".into()
+            },
+            DUMMY_SP,
+        );
+
+        let exported = if is_identifier(&exported) {
+            ModuleExportName::Ident(Ident::new_no_ctxt(exported, DUMMY_SP))
+        } else {
+            ModuleExportName::Str(Str::from(exported))
+        };
+
+        vec![
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(const_decl(
+                local.clone(),
+                value,
+            ))))),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                span: DUMMY_SP,
+                specifiers: vec![ExportSpecifier::Named(ExportNamedSpecifier {
+                    span: DUMMY_SP,
+                    orig: ModuleExportName::Ident(local),
+                    exported: Some(exported),
+                    is_type_only: false,
+                })],
+                src: None,
+                type_only: false,
+                with: None,
+            })),
+        ]
+    }
+
+    /// Records the helper bound to the stylesheet, returns `true` if the
+    /// declarator binds one.
+    ///
+    /// The bind is dropped by [`Self::unbind_helpers`] once all references are
+    /// known.
+    ///
+    /// ```js
+    /// const cx = classNames.bind(styles);
+    /// // ↓ ↓ ↓ ↓ ↓ ↓
+    /// const cx = classNames;
+    /// ```
+    fn bind(&mut self, declarator: &VarDeclarator) -> bool {
+        let (Pat::Ident(binding), Some(init)) = (&declarator.name, &declarator.init) else {
+            return false;
+        };
+
+        let Expr::Call(call) = &**init else {
+            return false;
+        };
+
+        let Callee::Expr(callee) = &call.callee else {
+            return false;
+        };
+
+        let Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(method),
+            ..
+        }) = &**callee
+        else {
+            return false;
+        };
+
+        let Expr::Ident(helper) = &**obj else {
+            return false;
+        };
+
+        if method.sym != "bind" || !self.binders.contains(&helper.to_id()) {
+            return false;
+        }
+
+        let [ExprOrSpread { spread: None, expr }] = call.args.as_slice() else {
+            return false;
+        };
+
+        let Expr::Ident(styles) = &**expr else {
+            return false;
+        };
+
+        let Some(filepath) = self.imports.get(&styles.to_id()).cloned() else {
+            return false;
+        };
+
+        let bound = Bound {
+            filepath,
+            helper: helper.clone(),
+            styles: styles.to_id(),
+            rewritten: 0,
+        };
+
+        self.bound.insert(binding.id.to_id(), bound);
+
+        true
+    }
+
+    /// Unbinds helpers whose every reference is a call with all arguments
+    /// replaced by generated names.
+    ///
+    /// Other helpers like exported ones, passed to functions or called before
+    /// they are visited keep mapping names of the stylesheet, so the
+    /// stylesheet import is kept.
+    fn unbind_helpers(&mut self, items: &mut Vec<ModuleItem>) {
+        let mut unbound = HashMap::new();
+
+        for (id, bound) in std::mem::take(&mut self.bound) {
+            // The declaration is a reference as well
+            let references = self.references.get(&id).copied().unwrap_or_default();
+
+            if bound.rewritten + 1 == references {
+                unbound.insert(id, bound.helper);
+            } else {
+                // The helper maps names as before, so it is not a problem
+                self.keep_import(&bound.styles);
+            }
+        }
+
+        if !unbound.is_empty() {
+            items.visit_mut_with(&mut Unbinder(unbound));
+        }
+    }
+
+    /// Replaces class names passed to the bound helper with generated names.
+    ///
+    /// ```js
+    /// cx("root", { active: isActive }, ["icon"]);
+    /// // ↓ ↓ ↓ ↓ ↓ ↓
+    /// cx("root___x1Fz3", { "active___Qw1sJ": isActive }, ["icon___b7Ds2"]);
+    /// ```
+    fn bound_call(&mut self, call: &mut CallExpr) {
+        let Callee::Expr(callee) = &call.callee else {
+            return;
+        };
+
+        let Expr::Ident(helper) = &**callee else {
+            return;
+        };
+
+        let Some(filepath) = self.bound.get(&helper.to_id()).map(|b| b.filepath.clone()) else {
+            return;
+        };
+
+        let mut rewritten = true;
+
+        for arg in call.args.iter_mut() {
+            rewritten &= arg.spread.is_none() && self.bound_class_arg(&mut arg.expr, &filepath);
+        }
+
+        if rewritten {
+            if let Some(bound) = self.bound.get_mut(&helper.to_id()) {
+                bound.rewritten += 1;
+            }
+        }
+    }
+
+    /// Replaces class names of the argument of the bound helper, returns
+    /// `true` if the argument has no other names the helper could map.
+    fn bound_class_arg(&mut self, expr: &mut Expr, filepath: &Path) -> bool {
+        match expr {
+            // "root"
+            Expr::Lit(Lit::Str(str_lit)) => {
+                let key = str_lit.value.as_str().expect("non-utf8 string").to_string();
+
+                if let Some(name) = self.bound_name(&key, filepath, str_lit.span) {
+                    *expr = Expr::from(name);
+                }
+
+                true
+            }
+
+            // null, false
+            Expr::Lit(Lit::Null(_) | Lit::Bool(_) | Lit::Num(_)) => true,
+
+            // ["root", "icon"]
+            Expr::Array(array) => {
+                let mut rewritten = true;
+
+                for element in array.elems.iter_mut().flatten() {
+                    rewritten &= element.spread.is_none()
+                        && self.bound_class_arg(&mut element.expr, filepath);
+                }
+
+                rewritten
+            }
+
+            // { active: isActive, "is-disabled": disabled, icon }
+            Expr::Object(object) => {
+                let mut rewritten = true;
+
+                for prop in object.props.iter_mut() {
+                    let PropOrSpread::Prop(prop) = prop else {
+                        rewritten = false;
+
+                        continue;
+                    };
+
+                    let (key, value, span) = match &**prop {
+                        Prop::Shorthand(ident) => (
+                            ident.sym.to_string(),
+                            Box::new(Expr::Ident(ident.clone())),
+                            ident.span,
+                        ),
+                        Prop::KeyValue(KeyValueProp {
+                            key: PropName::Ident(ident),
+                            value,
+                        }) => (ident.sym.to_string(), value.clone(), ident.span),
+                        Prop::KeyValue(KeyValueProp {
+                            key: PropName::Str(str_lit),
+                            value,
+                        }) => (
+                            str_lit.value.as_str().expect("non-utf8 key").to_string(),
+                            value.clone(),
+                            str_lit.span,
+                        ),
+                        _ => {
+                            rewritten = false;
+
+                            continue;
+                        }
+                    };
+
+                    let Some(name) = self.bound_name(&key, filepath, span) else {
+                        continue;
+                    };
+
+                    **prop = Prop::KeyValue(KeyValueProp {
+                        key: PropName::Str(Str::from(name)),
+                        value,
+                    });
+                }
+
+                rewritten
+            }
+
+            Expr::Paren(paren) => self.bound_class_arg(&mut paren.expr, filepath),
+
+            // size
+            _ => false,
+        }
+    }
+
+    /// Returns the generated name for the class passed to the bound helper.
+    ///
+    /// Returns `None` if the stylesheet does not export the name, the helper
+    /// passes such names as is.
+    fn bound_name(&mut self, key: &str, filepath: &Path, span: Span) -> Option<String> {
+        if self.stylesheets.get(filepath).is_some() {
+            self.stylesheet_name(filepath, key)?;
+        }
+
+        Some(self.resolve(key, filepath.to_path_buf(), span))
+    }
+
+    /// Returns the string the template or the class joining helper call
+    /// evaluates to if all its parts are constant.
+    ///
+    /// ```js
+    /// `${"title"} ${"active"}`;
+    /// clsx("title", { active: true, disabled: false });
+    /// // ↓ ↓ ↓ ↓ ↓ ↓
+    /// "title active";
+    /// "title active";
+    /// ```
+    fn fold(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Tpl(tpl) => fold_tpl(tpl),
+
+            Expr::Call(call) => {
+                let Callee::Expr(callee) = &call.callee else {
+                    return None;
+                };
+
+                let Expr::Ident(helper) = &**callee else {
+                    return None;
+                };
+
+                if !self.joiners.contains(&helper.to_id())
+                    && !self.bound.contains_key(&helper.to_id())
+                {
+                    return None;
+                }
+
+                let mut classes = vec![];
+
+                for arg in &call.args {
+                    if arg.spread.is_some() {
+                        return None;
+                    }
+
+                    constant_classes(&arg.expr, &mut classes)?;
+                }
+
+                Some(classes.join(" "))
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Returns the generated name for the access to the stylesheet import.
+    fn inline_member(&mut self, member: &MemberExpr) -> Option<Expr> {
+        let Expr::Ident(obj) = &*member.obj else {
+            return None;
+        };
+
+        // Check variable usage for css modules
+        if !self.imports.contains_key(&obj.to_id()) {
+            return None;
+        }
+
+        let generated_name = match &member.prop {
+            // styles.title
+            MemberProp::Ident(i) => self.generated_name(&obj.to_id(), &i.sym, member.span),
+
+            MemberProp::Computed(computed) => match &*computed.expr {
+                // styles['Component--disabled']
+                Expr::Lit(Lit::Str(str_lit)) => self.generated_name(
+                    &obj.to_id(),
+                    str_lit.value.as_atom().expect("non-utf8 key"),
+                    member.span,
+                ),
+
+                // styles[prefix + "title"]
+                _ => {
+                    self.cannot_inline(
+                        &obj.to_id(),
+                        Kind::ComputedAccess,
+                        computed.span,
+                        "Computed hit cannot be injected",
+                    );
+
+                    return None;
+                }
+            },
+
+            _ => return None,
+        };
+
+        Some(Expr::from(generated_name))
+    }
+
+    /// Checks that the expression is the binding of the stylesheet import.
+    fn is_import(&self, expr: &Expr) -> bool {
+        matches!(expr, Expr::Ident(ident) if self.imports.contains_key(&ident.to_id()))
+    }
+
+    /// Keeps the import because the usage of the binding cannot be inlined.
+    fn cannot_inline(&mut self, local: &Id, kind: Kind, span: Span, message: &str) {
+        self.keep_import(local);
+
+        diagnostics::report(&self.config, kind, span, message);
+    }
+
+    /// Keeps the import of the stylesheet binding.
+    ///
+    /// Every local of the stylesheet may be accessed through the binding.
+    fn keep_import(&mut self, local: &Id) {
+        self.escaped.insert(local.clone());
+
+        if let Some(filepath) = self.imports.get(local).cloned() {
+            self.record_usage(&filepath, Usage::ALL);
+        }
+    }
+
+    /// Keeps the import because the binding is used as a value.
+    fn escape(&mut self, local: &Id, span: Span) {
+        let message = format!(
+            "\"{}\" is used as a value and cannot be inlined, the import is kept",
+            local.0
+        );
+
+        self.cannot_inline(local, Kind::EscapingBinding, span, &message);
+    }
+
+    /// Removes specifiers of the css module import whose usages are all
+    /// inlined.
+    ///
+    /// Type-only specifiers are kept, they are moved to a type-only import if
+    /// no other specifiers are left.
+    ///
+    /// ```js
+    /// import styles, { type Names } from "./Component.module.css";
+    /// // ↓ ↓ ↓ ↓ ↓ ↓
+    /// import "./Component.module.css";
+    /// import type { Names } from "./Component.module.css";
+    /// ```
+    fn strip_import(&mut self, import: &mut ImportDecl) -> Option<ImportDecl> {
+        if import.type_only || !self.is_css_module(&import.src.value) {
+            return None;
+        }
+
+        import.specifiers.retain(|specifier| {
+            let local = match specifier {
+                ImportSpecifier::Named(named) if named.is_type_only => return true,
+                ImportSpecifier::Named(named) => &named.local,
+                ImportSpecifier::Default(default) => &default.local,
+                ImportSpecifier::Namespace(namespace) => &namespace.local,
+                #[cfg(swc_ast_unknown)]
+                _ => return true,
+            };
+
+            self.escaped.contains(&local.to_id())
+        });
+
+        let type_only = !import.specifiers.is_empty()
+            && import.specifiers.iter().all(
+                |specifier| matches!(specifier, ImportSpecifier::Named(named) if named.is_type_only),
+            );
+
+        if !type_only {
+            return None;
+        }
+
+        let mut specifiers = std::mem::take(&mut import.specifiers);
+
+        for specifier in &mut specifiers {
+            if let ImportSpecifier::Named(named) = specifier {
+                named.is_type_only = false;
+            }
+        }
+
+        Some(ImportDecl {
+            specifiers,
+            type_only: true,
+            ..import.clone()
+        })
+    }
+
+    /// Returns the stylesheet import and the key checked by the expression.
+    ///
+    /// ```js
+    /// "title" in styles;
+    /// styles.hasOwnProperty("title");
+    /// Object.hasOwn(styles, "title");
+    /// Object.prototype.hasOwnProperty.call(styles, "title");
+    /// ```
+    fn membership_check(&self, expr: &Expr) -> Option<(Id, Expr)> {
+        let (obj, key) = match expr {
+            Expr::Bin(BinExpr {
+                op: BinaryOp::In,
+                left,
+                right,
+                ..
+            }) => (&**right, &**left),
+
+            Expr::Call(CallExpr {
+                callee: Callee::Expr(callee),
+                args,
+                ..
+            }) => match args.as_slice() {
+                [ExprOrSpread {
+                    spread: None,
+                    expr: key,
+                }] => match &**callee {
+                    Expr::Member(MemberExpr {
+                        obj,
+                        prop: MemberProp::Ident(method),
+                        ..
+                    }) if method.sym == "hasOwnProperty" => (&**obj, &**key),
+                    _ => return None,
+                },
+
+                [ExprOrSpread {
+                    spread: None,
+                    expr: obj,
+                }, ExprOrSpread {
+                    spread: None,
+                    expr: key,
+                }] if is_path(callee, &["Object", "hasOwn"])
+                    || is_path(callee, &["Object", "prototype", "hasOwnProperty", "call"]) =>
+                {
+                    (&**obj, &**key)
+                }
+
+                _ => return None,
+            },
+
+            _ => return None,
+        };
+
+        let Expr::Ident(obj) = obj else {
+            return None;
+        };
+
+        self.imports
+            .contains_key(&obj.to_id())
+            .then(|| (obj.to_id(), key.clone()))
+    }
+
+    /// Returns `true` if the stylesheet exports the key.
+    ///
+    /// Emits an error if the key is not constant or the stylesheet cannot be read.
+    fn has_name(&mut self, module: &Id, key: &Expr, span: Span) -> Option<bool> {
+        let Expr::Lit(Lit::Str(str_lit)) = key else {
+            self.cannot_inline(
+                module,
+                Kind::ComputedAccess,
+                key.span(),
+                "Computed hit cannot be injected",
+            );
+
+            return None;
+        };
+
+        let key = str_lit.value.as_str().expect("non-utf8 key");
+        let filepath = self.imports.get(module).unwrap().to_path_buf();
+
+        if self.stylesheets.get(&filepath).is_none() {
+            let message = format!(
+                "Cannot read {} to check if it has \"{}\"",
+                filepath.to_string_lossy(),
+                key
+            );
+
+            self.cannot_inline(module, Kind::UnreadableStylesheet, span, &message);
+
+            return None;
+        }
+
+        let Some(name) = self.stylesheet_name(&filepath, key) else {
+            return Some(false);
+        };
+
+        // "title" in styles && styles.title
+        self.record_usage(&filepath, &name);
+
+        Some(true)
+    }
+
+    /// Returns class name from list.
+    fn generated_name(&mut self, module: &Id, name: &Atom, span: Span) -> String {
+        let filepath = self.imports.get(module).unwrap().to_path_buf();
+
+        self.resolve(name.as_str(), filepath, span)
+    }
+
+    fn generated_name_for_named_import(&mut self, local: &Id, span: Span) -> String {
+        let (imported, filepath) = self.named_imports.get(local).unwrap().clone();
+
+        self.resolve(imported.as_str(), filepath, span)
+    }
+}
+
+impl Scope for Injector {
+    fn scoped_name(&mut self, name: &str, filepath: &Path) -> Option<String> {
+        if name.starts_with("--") && !self.config.dashed_idents {
+            return None;
+        }
+
+        Some(self.generate(name, filepath.to_path_buf()))
+    }
+
+    fn value(&mut self, name: &str, filepath: &Path) -> Option<String> {
+        match self.stylesheets.constant(filepath, name, &|source, dir| {
+            self.resolver.resolve(source, dir)
+        }) {
+            Ok(value) => value,
+            Err(error) => {
+                diagnostics::report(
+                    &self.config,
+                    Kind::CyclicValue,
+                    DUMMY_SP,
+                    &error.to_string(),
+                );
+
+                None
+            }
+        }
+    }
+}
+
+impl VisitMut for Injector {
+    fn visit_mut_program(&mut self, program: &mut Program) {
+        let mut references = References::default();
+        program.visit_with(&mut references);
+        self.references = references.0;
+
+        program.visit_mut_children_with(self);
+
+        self.flush_usage();
+    }
+
+    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        items.visit_mut_children_with(self);
+
+        self.unbind_helpers(items);
+
+        let mut result = Vec::with_capacity(items.len());
+
+        for item in items.drain(..) {
+            match self.reexport(&item) {
+                Some(reexport) => result.extend(reexport),
+                None => result.push(item),
+            }
+        }
+
+        for mut item in result {
+            let type_import = match &mut item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => self.strip_import(import),
+                _ => None,
+            };
+
+            items.push(item);
+            items.extend(
+                type_import.map(|import| ModuleItem::ModuleDecl(ModuleDecl::Import(import))),
+            );
+        }
+    }
+
+    fn visit_mut_var_declarator(&mut self, declarator: &mut VarDeclarator) {
+        // classNames.bind(styles) is not visited, so styles is not an escaping
+        // binding unless the helper stays bound
+        if self.bind(declarator) {
+            return;
+        }
+
+        declarator.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_expr(&mut self, expression: &mut Expr) {
+        // cx("root") before styles.root inside is inlined
+        if let Expr::Call(call) = expression {
+            self.bound_call(call);
+        }
+
+        // styles.hasOwnProperty("title") before styles.hasOwnProperty is inlined
+        if let Some((module, key)) = self.membership_check(expression) {
+            if let Some(value) = self.has_name(&module, &key, expression.span()) {
+                *expression = Expr::Lit(Lit::Bool(Bool {
+                    span: expression.span(),
+                    value,
+                }));
+            }
+
+            return;
+        }
+
+        // styles.title or styles?.title before styles is visited as an escaping binding
+        let member = match expression {
+            Expr::Member(member) => Some(member),
+            Expr::OptChain(OptChainExpr { base, .. }) => match &mut **base {
+                OptChainBase::Member(member) => Some(member),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        if let Some(member) = member.filter(|member| self.is_import(&member.obj)) {
+            match self.inline_member(member) {
+                Some(exp) => expression.clone_from(&exp),
+                // styles[prefix + "title"]
+                None => member.prop.visit_mut_with(self),
+            }
+
+            return;
+        }
+
+        expression.visit_mut_children_with(self);
+
+        if self.config.fold_constants {
+            if let Some(folded) = self.fold(expression) {
+                *expression = Expr::from(folded);
+
+                return;
+            }
+        }
+
+        // import("./Component.module.css")
+        if let Expr::Call(call) = expression {
+            if matches!(call.callee, Callee::Import(_)) {
+                if let Some(exp) = self.dynamic_import(call) {
+                    expression.clone_from(&exp)
+                }
+
+                return;
+            }
+        }
+
+        if self.imports.is_empty() && self.named_imports.is_empty() {
+            return;
+        }
+
+        // import { foo } from "./Component.module.css"
+        //
+        // className(foo)
+        if let Expr::Ident(ident) = expression {
+            // fn(styles)
+            if self.imports.contains_key(&ident.to_id()) {
+                let (local, span) = (ident.to_id(), ident.span);
+
+                self.escape(&local, span);
+
+                return;
+            }
+
+            if !self.named_imports.contains_key(&ident.to_id()) {
+                return;
+            }
+
+            let generated_name = self.generated_name_for_named_import(&ident.to_id(), ident.span);
+
+            *expression = Expr::from(generated_name);
+        }
+    }
+
+    fn visit_mut_import_decl(&mut self, n: &mut ImportDecl) {
+        n.visit_mut_children_with(self);
+
+        // import classNames from "classnames/bind"
+        if !n.type_only
+            && self
+                .config
+                .bound_class_helpers
+                .iter()
+                .any(|helper| n.src.value.as_str() == Some(helper.as_str()))
+        {
+            for specifier in &n.specifiers {
+                match specifier {
+                    ImportSpecifier::Default(default) => {
+                        self.binders.insert(default.local.to_id());
+                    }
+                    ImportSpecifier::Namespace(namespace) => {
+                        self.binders.insert(namespace.local.to_id());
+                    }
+                    _ => {}
+                }
+            }
+
+            return;
+        }
+
+        // import clsx from "clsx"
+        // import { clsx } from "clsx"
+        if !n.type_only
+            && self
+                .config
+                .class_join_helpers
+                .iter()
+                .any(|helper| n.src.value.as_str() == Some(helper.as_str()))
+        {
+            for specifier in &n.specifiers {
+                match specifier {
+                    ImportSpecifier::Named(named) if named.is_type_only => {}
+                    ImportSpecifier::Named(named) => {
+                        self.joiners.insert(named.local.to_id());
+                    }
+                    ImportSpecifier::Default(default) => {
+                        self.joiners.insert(default.local.to_id());
+                    }
+                    _ => {}
+                }
+            }
+
+            return;
+        }
+
+        // Check if it's a css modules import
+
+        // import type { Foo } from "./Component.module.css"
+        if !self.is_css_module(&n.src.value) || n.specifiers.is_empty() || n.type_only {
+            return;
+        }
+
+        let src = &n.src.value;
+
+        // Extract variable name and process css modules
+        n.specifiers.iter().for_each(|specifier| match specifier {
+            // import { type Foo } from "./Component.module.css"
+            ImportSpecifier::Named(named) if named.is_type_only => {}
+
+            // import { foo as bar } from "./Component.module.css"
+            ImportSpecifier::Named(named) => {
+                let imported = match &named.imported {
+                    None => named.local.sym.clone(),
+                    Some(ModuleExportName::Ident(ident)) => ident.sym.clone(),
+                    // import { "foo-bar" as fooBar } from "./Component.module.css"
+                    Some(ModuleExportName::Str(str_lit)) => {
+                        str_lit.value.as_atom().expect("non-utf8 name").clone()
+                    }
+                    #[cfg(swc_ast_unknown)]
+                    Some(_) => return,
+                };
+
+                // import { default as styles } from "./Component.module.css"
+                if imported == "default" {
+                    if self.config.named_export {
+                        diagnostics::report(
+                            &self.config,
+                            Kind::DefaultImport,
+                            named.span,
+                            "Default import of css module is not allowed with named_export, use `import * as styles`",
+                        );
+                    }
+
+                    self.new_import(&named.local, src)
+                } else {
+                    self.new_named_import(&imported, &named.local, src)
+                }
+            }
+
+            // import styles from "./Component.module.css"
+            ImportSpecifier::Default(default) => {
+                if self.config.named_export {
+                    diagnostics::report(
+                        &self.config,
+                        Kind::DefaultImport,
+                        default.span,
+                        "Default import of css module is not allowed with named_export, use `import * as styles`",
+                    );
+                }
+
+                self.new_import(&default.local, src)
+            }
+
+            // import * as styles from "./Component.module.css"
+            ImportSpecifier::Namespace(namespace) => self.new_import(&namespace.local, src),
+            #[cfg(swc_ast_unknown)]
+            _ => {}
+        });
+
+        // Specifiers are removed by `strip_import` once all usages are known
+    }
+
+    // { styles } or { title }
+    fn visit_mut_prop(&mut self, prop: &mut Prop) {
+        prop.visit_mut_children_with(self);
+
+        let Prop::Shorthand(ident) = prop else {
+            return;
+        };
+
+        if self.named_imports.contains_key(&ident.to_id()) {
+            let value =
+                Expr::from(self.generated_name_for_named_import(&ident.to_id(), ident.span));
+
+            *prop = Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(IdentName::new(ident.sym.clone(), ident.span)),
+                value: Box::new(value),
+            });
+        } else if self.imports.contains_key(&ident.to_id()) {
+            let (local, span) = (ident.to_id(), ident.span);
+
+            self.escape(&local, span);
+        }
+    }
+
+    // export { styles, title }
+    fn visit_mut_named_export(&mut self, export: &mut NamedExport) {
+        if export.src.is_some() || export.type_only {
+            return;
+        }
+
+        for specifier in &export.specifiers {
+            if let ExportSpecifier::Named(ExportNamedSpecifier {
+                orig: ModuleExportName::Ident(ident),
+                is_type_only: false,
+                ..
+            }) = specifier
+            {
+                if self.imports.contains_key(&ident.to_id())
+                    || self.named_imports.contains_key(&ident.to_id())
+                {
+                    self.escape(&ident.to_id(), ident.span);
+                }
+            }
+        }
+    }
+}
+
+fn module_export_name(name: &ModuleExportName) -> Atom {
+    name.atom().as_ref().to_owned()
+}
+
+/// Checks that the expression is a member chain like `Object.hasOwn`.
+fn is_path(expr: &Expr, path: &[&str]) -> bool {
+    match (expr, path) {
+        (Expr::Ident(ident), [name]) => ident.sym == *name,
+        (
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }),
+            [rest @ .., name],
+        ) => prop.sym == *name && is_path(obj, rest),
+        _ => false,
+    }
+}
+
+/// Returns the string the template evaluates to if all its expressions are
+/// constant.
+fn fold_tpl(tpl: &Tpl) -> Option<String> {
+    let mut result = String::new();
+
+    for (index, quasi) in tpl.quasis.iter().enumerate() {
+        result.push_str(quasi.cooked.as_ref()?.as_str()?);
+
+        if let Some(expr) = tpl.exprs.get(index) {
+            match &**expr {
+                Expr::Lit(Lit::Str(str_lit)) => result.push_str(str_lit.value.as_str()?),
+                Expr::Tpl(tpl) => result.push_str(&fold_tpl(tpl)?),
+                _ => return None,
+            }
+        }
+    }
+
+    Some(result)
+}
+
+/// Collects classes the argument of `clsx` or `classnames` adds if it is
+/// constant.
+fn constant_classes(expr: &Expr, classes: &mut Vec<String>) -> Option<()> {
+    match expr {
+        Expr::Lit(Lit::Str(str_lit)) => {
+            let class = str_lit.value.as_str()?;
+
+            if !class.is_empty() {
+                classes.push(class.to_string());
+            }
+        }
+
+        Expr::Tpl(tpl) => {
+            let class = fold_tpl(tpl)?;
+
+            if !class.is_empty() {
+                classes.push(class);
+            }
+        }
+
+        // false, null
+        Expr::Lit(Lit::Bool(_) | Lit::Null(_)) => {}
+
+        Expr::Array(array) => {
+            for element in array.elems.iter().flatten() {
+                if element.spread.is_some() {
+                    return None;
+                }
+
+                constant_classes(&element.expr, classes)?;
+            }
+        }
+
+        // { title: true, [styles.active]: false }
+        Expr::Object(object) => {
+            for prop in &object.props {
+                let PropOrSpread::Prop(prop) = prop else {
+                    return None;
+                };
+
+                let Prop::KeyValue(KeyValueProp { key, value }) = &**prop else {
+                    return None;
+                };
+
+                let Expr::Lit(Lit::Bool(enabled)) = &**value else {
+                    return None;
+                };
+
+                let class = match key {
+                    PropName::Ident(ident) => ident.sym.to_string(),
+                    PropName::Str(str_lit) => str_lit.value.as_str()?.to_string(),
+                    PropName::Computed(computed) => match &*computed.expr {
+                        Expr::Lit(Lit::Str(str_lit)) => str_lit.value.as_str()?.to_string(),
+                        _ => return None,
+                    },
+                    _ => return None,
+                };
+
+                if enabled.value {
+                    classes.push(class);
+                }
+            }
+        }
+
+        Expr::Paren(paren) => constant_classes(&paren.expr, classes)?,
+
+        _ => return None,
+    }
+
+    Some(())
+}
+
+fn object_lit(entries: Vec<(String, Expr)>) -> Expr {
+    let props = entries
+        .into_iter()
+        .map(|(name, value)| {
+            let key = if is_identifier(&name) || name == "default" {
+                PropName::Ident(IdentName::new(name.into(), DUMMY_SP))
+            } else {
+                PropName::Str(Str::from(name))
+            };
+
+            PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key,
+                value: Box::new(value),
+            })))
+        })
+        .collect();
+
+    Expr::Object(ObjectLit {
+        span: DUMMY_SP,
+        props,
+    })
+}
 
-        // import styles from "./Component.module.css";
-        // ↓ ↓ ↓ ↓ ↓ ↓
-        // import "./Component.module.css";
-        n.specifiers.clear();
+fn const_decl(name: Ident, init: Expr) -> VarDecl {
+    VarDecl {
+        span: DUMMY_SP,
+        ctxt: Default::default(),
+        kind: VarDeclKind::Const,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(name.into()),
+            init: Some(Box::new(init)),
+            definite: false,
+        }],
     }
 }
@@ -1,13 +1,27 @@
 mod config;
+mod declarations;
+mod diagnostics;
 pub mod generic_names;
+mod hash_path;
+mod identifier;
 mod injector;
 pub mod loader_utils;
+mod overrides;
+mod resolver;
+mod stylesheet;
+mod usage;
 
-pub use config::Config;
+use std::env;
+use std::path::Path;
+
+pub use config::{
+    Config, Diagnostics, HashInput, LocalsConvention, Override, OverrideTarget, Severity,
+};
 pub use injector::Injector;
 use swc_core::ecma::{ast::Program, visit::visit_mut_pass};
 use swc_core::plugin::metadata::TransformPluginMetadataContextKind;
 use swc_core::plugin::{plugin_transform, proxies::TransformPluginProgramMetadata};
+pub use usage::Usage;
 
 #[plugin_transform]
 pub fn process_transform(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
@@ -18,6 +32,8 @@ pub fn process_transform(program: Program, metadata: TransformPluginProgramMetad
     )
     .expect("invalid config");
 
+    config.validate();
+
     let filepath = metadata
         .get_context(&TransformPluginMetadataContextKind::Filename)
         .expect("failed to get filepath");
@@ -32,3 +48,28 @@ pub fn process_transform(program: Program, metadata: TransformPluginProgramMetad
         config,
     )))
 }
+
+/// Returns the stylesheet with scoped names, `:export` blocks, `@value`
+/// definitions and `composes` removed, and values inlined, like css-loader
+/// with the same config outputs it.
+///
+/// Names are the same the plugin inlines in JS. Relative `root` of the config
+/// and relative paths are resolved from the current directory.
+///
+/// ```
+/// use std::path::Path;
+/// use swc_plugin_css_modules::{rewrite, Config};
+///
+/// let config: Config = serde_json::from_str(r#"{"generate_scoped_name": "[local]_x"}"#).unwrap();
+///
+/// assert_eq!(
+///     rewrite(config, Path::new("a.module.css"), ".a { composes: b; } .b { }"),
+///     ".a_x {  } .b_x { }",
+/// );
+/// ```
+pub fn rewrite(config: Config, path: &Path, source: &str) -> String {
+    let cwd = env::current_dir().unwrap_or_default();
+    let path = cwd.join(path);
+
+    Injector::new(&cwd.to_string_lossy(), &path.to_string_lossy(), config).rewrite(&path, source)
+}
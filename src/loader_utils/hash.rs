@@ -1,5 +1,3 @@
-use base32ct::Base32;
-use base32ct::Encoding as Base32Encoding;
 use base64ct::Base64;
 use base64ct::Encoding as Base64Encoding;
 use digest::DynDigest;
@@ -20,6 +18,11 @@ pub fn get_hash_digest(
     max_length: usize,
 ) -> String {
     let hash = use_hasher(algorithm, data);
+
+    if digest_type == "base32" {
+        return encode_base32(&hash, max_length);
+    }
+
     let encoded = use_digest(digest_type, hash);
     let result = encoded.get(0..max_length).unwrap_or(encoded.as_str());
 
@@ -61,16 +64,45 @@ fn select_hasher(hash_type: &str) -> Box<dyn DynDigest> {
 
 fn use_digest(digest_type: &str, input: Box<[u8]>) -> String {
     match digest_type {
-        "base32" => Base32::encode_string(&input),
         "base64" => Base64::encode_string(&input),
         "hex" => base16ct::lower::encode_string(&input),
         _ => unimplemented!("unsupported hash digest: {}", digest_type),
     }
 }
 
+/// Encodes the hash like `encodeBufferToBase` of loader-utils, which uses its
+/// own alphabet without `0`, `i`, `l` and `o` instead of RFC 4648.
+///
+/// The hash is a little-endian number, the result is its `max_length` least
+/// significant digits with the most significant first.
+fn encode_base32(input: &[u8], max_length: usize) -> String {
+    const ALPHABET: &[u8; 32] = b"123456789abcdefghjkmnpqrstuvwxyz";
+
+    // Input bits are only enough to generate this many characters
+    let length = max_length.min((input.len() * 8).div_ceil(5));
+
+    let mut number = input.to_vec();
+    let mut digits = Vec::with_capacity(length);
+
+    for _ in 0..length {
+        let mut remainder = 0;
+
+        for byte in number.iter_mut().rev() {
+            let value = (remainder << 8) | u32::from(*byte);
+
+            *byte = (value / 32) as u8;
+            remainder = value % 32;
+        }
+
+        digits.push(ALPHABET[remainder as usize]);
+    }
+
+    digits.iter().rev().map(|&digit| digit as char).collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::get_hash_digest;
+    use super::{encode_base32, get_hash_digest};
 
     #[test]
     fn get_hash_digest_xxhash64() {
@@ -160,6 +192,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_hash_digest_base32() {
+        // 0x20 is 32 in little-endian, "21" with leading "1" zeros
+        assert_eq!(encode_base32(&[0x20, 0, 0, 0], 9999), "1111121");
+        assert_eq!(encode_base32(&[0xff, 0xff, 0xff, 0xff], 9999), "4zzzzzz");
+        assert_eq!(
+            get_hash_digest("test string".as_bytes(), "md5", "base32", 5).len(),
+            5
+        );
+        assert_eq!(
+            get_hash_digest("test string".as_bytes(), "md5", "base32", 9999).len(),
+            26
+        );
+    }
+
     #[test]
     fn get_hash_digest_sha512() {
         assert_eq!(
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use swc_core::common::DUMMY_SP;
+
+use crate::config::{Config, Override, OverrideTarget};
+use crate::diagnostics::{self, Kind};
+
+lazy_static! {
+    /// Globs of overrides compiled once for all files.
+    static ref GLOB_SETS: Mutex<HashMap<Vec<String>, Result<Regex, String>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Overrides with compiled globs.
+pub struct Overrides {
+    overrides: Vec<(Regex, Override)>,
+}
+
+impl Overrides {
+    /// Overrides with invalid globs are reported and never match.
+    pub fn new(config: &Config) -> Self {
+        let overrides = config
+            .overrides
+            .iter()
+            .filter_map(|entry| match glob_set(&entry.files) {
+                Ok(globs) => Some((globs, entry.clone())),
+                Err(error) => {
+                    diagnostics::report(
+                        config,
+                        Kind::InvalidConfig,
+                        DUMMY_SP,
+                        &format!("Invalid glob of overrides, the override is ignored: {error}"),
+                    );
+
+                    None
+                }
+            })
+            .collect();
+
+        Self { overrides }
+    }
+
+    /// Returns the config with overrides matching the importing file applied.
+    ///
+    /// `file` is relative to the root.
+    pub fn for_file(&self, config: &Config, file: &str) -> Config {
+        let mut config = config.clone();
+
+        for entry in self.matching(OverrideTarget::File, file) {
+            if let Some(generate_scoped_name) = &entry.generate_scoped_name {
+                config.generate_scoped_name = generate_scoped_name.clone();
+            }
+
+            if let Some(hash_prefix) = &entry.hash_prefix {
+                config.hash_prefix = hash_prefix.clone();
+            }
+
+            if let Some(css_modules_suffix) = &entry.css_modules_suffix {
+                config.css_modules_suffix = css_modules_suffix.clone();
+            }
+
+            if let Some(strict) = entry.strict {
+                config.strict = strict;
+            }
+        }
+
+        config
+    }
+
+    /// Returns `generate_scoped_name` and `hash_prefix` for the stylesheet,
+    /// `None` if no override matches it.
+    ///
+    /// `stylesheet` is relative to the root.
+    pub fn naming(&self, config: &Config, stylesheet: &str) -> Option<(String, String)> {
+        let mut matching = self
+            .matching(OverrideTarget::Stylesheet, stylesheet)
+            .peekable();
+
+        matching.peek()?;
+
+        let mut pattern = config.generate_scoped_name.clone();
+        let mut hash_prefix = config.hash_prefix.clone();
+
+        for entry in matching {
+            if let Some(generate_scoped_name) = &entry.generate_scoped_name {
+                pattern = generate_scoped_name.clone();
+            }
+
+            if let Some(prefix) = &entry.hash_prefix {
+                hash_prefix = prefix.clone();
+            }
+        }
+
+        Some((pattern, hash_prefix))
+    }
+
+    /// Returns overrides of the target matching the path in order.
+    fn matching<'a>(
+        &'a self,
+        target: OverrideTarget,
+        path: &'a str,
+    ) -> impl Iterator<Item = &'a Override> {
+        self.overrides
+            .iter()
+            .filter(move |(globs, entry)| entry.target == target && globs.is_match(path))
+            .map(|(_, entry)| entry)
+    }
+}
+
+/// Compiles globs to a regex matching any of them.
+fn glob_set(globs: &[String]) -> Result<Regex, String> {
+    let mut glob_sets = GLOB_SETS.lock().unwrap();
+
+    glob_sets
+        .entry(globs.to_vec())
+        .or_insert_with(|| {
+            let alternatives = globs
+                .iter()
+                .map(|glob| glob_regex(glob))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Regex::new(&format!("^(?:{})$", alternatives.join("|")))
+                .map_err(|error| error.to_string())
+        })
+        .clone()
+}
+
+/// Converts the glob to a regex:
+///
+/// - `**/` matches any directories
+/// - `*` matches anything but `/`
+/// - `?` matches a character but `/`
+/// - `{a,b}` matches any of alternatives
+///
+/// Returns an error if a brace is not closed.
+fn glob_regex(glob: &str) -> Result<String, String> {
+    let mut regex = String::new();
+    let mut rest = glob.strip_prefix("./").unwrap_or(glob);
+    let mut braces = 0;
+
+    while let Some(c) = rest.chars().next() {
+        let (part, len) = match c {
+            '*' if rest.starts_with("**/") => ("(?:.*/)?".to_string(), 3),
+            '*' if rest.starts_with("**") => (".*".to_string(), 2),
+            '*' => ("[^/]*".to_string(), 1),
+            '?' => ("[^/]".to_string(), 1),
+            '{' => {
+                braces += 1;
+
+                ("(?:".to_string(), 1)
+            }
+            '}' if braces > 0 => {
+                braces -= 1;
+
+                (")".to_string(), 1)
+            }
+            ',' if braces > 0 => ("|".to_string(), 1),
+            _ => (regex::escape(&c.to_string()), c.len_utf8()),
+        };
+
+        regex.push_str(&part);
+        rest = &rest[len..];
+    }
+
+    if braces > 0 {
+        return Err(format!("\"{glob}\" has an unclosed {{"));
+    }
+
+    Ok(regex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::glob_set;
+
+    #[test]
+    fn globs() {
+        let globs = glob_set(&[
+            "packages/legacy/**".to_string(),
+            "**/shared/*.module.{css,scss}".to_string(),
+        ])
+        .unwrap();
+
+        assert!(globs.is_match("packages/legacy/src/App.js"));
+        assert!(globs.is_match("shared/Button.module.css"));
+        assert!(globs.is_match("packages/ui/shared/Button.module.scss"));
+
+        assert!(!globs.is_match("packages/legacy-ui/App.js"));
+        assert!(!globs.is_match("packages/ui/shared/nested/Button.module.css"));
+        assert!(!globs.is_match("shared/Button.module.less"));
+    }
+
+    #[test]
+    fn invalid_globs() {
+        assert_eq!(
+            glob_set(&["src/*.{css,scss".to_string()]).unwrap_err(),
+            "\"src/*.{css,scss\" has an unclosed {"
+        );
+    }
+}
@@ -0,0 +1,446 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use path_absolutize::*;
+use serde_json::Value;
+use swc_core::common::DUMMY_SP;
+
+use crate::config::Config;
+use crate::diagnostics::{self, Kind};
+
+/// Conditions of package `exports` a bundler matches for stylesheets.
+const CONDITIONS: &[&str] = &["style", "browser", "import", "require", "default"];
+
+/// Resolves import specifiers of stylesheets to the paths the bundler
+/// resolves them to, so names are hashed from the same paths.
+#[derive(Debug)]
+pub struct Resolver {
+    /// Aliases with absolute targets, the longest first.
+    aliases: Vec<(String, PathBuf)>,
+    /// Patterns of tsconfig `paths` with their targets.
+    paths: Vec<(String, Vec<String>)>,
+    /// The directory targets of `paths` are relative to.
+    paths_base: PathBuf,
+    /// tsconfig `baseUrl`.
+    base_url: Option<PathBuf>,
+    config: Config,
+}
+
+impl Resolver {
+    /// `alias` targets and the `tsconfig` path are relative to the context.
+    pub fn new(context: &Path, config: &Config) -> Self {
+        let mut aliases: Vec<_> = config
+            .alias
+            .iter()
+            .map(|(alias, target)| (alias.clone(), normalize(&context.join(target))))
+            .collect();
+
+        aliases.sort_by_key(|(alias, _)| std::cmp::Reverse(alias.len()));
+
+        let mut resolver = Self {
+            aliases,
+            paths: Vec::new(),
+            paths_base: PathBuf::new(),
+            base_url: None,
+            config: config.clone(),
+        };
+
+        if let Some(tsconfig) = &config.tsconfig {
+            let path = context.join(tsconfig);
+
+            if let Err(error) = resolver.read_tsconfig(&path, &mut Vec::new()) {
+                diagnostics::report(
+                    config,
+                    Kind::FileAccess,
+                    DUMMY_SP,
+                    &format!("Cannot read {}: {}", path.to_string_lossy(), error),
+                );
+            }
+        }
+
+        // `paths` are relative to `baseUrl` if any
+        if let Some(base_url) = &resolver.base_url {
+            resolver.paths_base = base_url.clone();
+        }
+
+        resolver
+    }
+
+    /// Reads `baseUrl` and `paths` of `compilerOptions`, options of configs
+    /// the config `extends` are overridden by its own ones.
+    ///
+    /// `extending` are configs extending the config to detect cycles.
+    fn read_tsconfig(&mut self, path: &Path, extending: &mut Vec<PathBuf>) -> Result<(), String> {
+        let path = normalize(path);
+
+        if extending.contains(&path) {
+            return Err("extends itself".to_string());
+        }
+
+        let source = fs::read_to_string(&path).map_err(|error| error.to_string())?;
+        let tsconfig: Value =
+            serde_json::from_str(&strip_comments(&source)).map_err(|error| error.to_string())?;
+
+        let dir = path.parent().unwrap();
+
+        let bases = match &tsconfig["extends"] {
+            Value::String(base) => vec![base.as_str()],
+            Value::Array(bases) => bases.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+
+        extending.push(path.clone());
+
+        for base in bases {
+            let base = extends_path(base, dir).ok_or_else(|| format!("Cannot find {base}"))?;
+
+            self.read_tsconfig(&base, extending)
+                .map_err(|error| format!("{}: {}", base.to_string_lossy(), error))?;
+        }
+
+        extending.pop();
+
+        let options = &tsconfig["compilerOptions"];
+
+        if let Some(base_url) = options["baseUrl"].as_str() {
+            self.base_url = Some(normalize(&dir.join(base_url)));
+        }
+
+        if let Some(paths) = options["paths"].as_object() {
+            self.paths_base = dir.to_path_buf();
+            self.paths = paths
+                .iter()
+                .map(|(pattern, targets)| {
+                    let targets = targets
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|target| target.as_str().map(str::to_string))
+                        .collect();
+
+                    (pattern.clone(), targets)
+                })
+                .collect();
+
+            // TypeScript picks the pattern with the longest prefix
+            self.paths.sort_by_key(|(pattern, _)| {
+                std::cmp::Reverse(pattern.split('*').next().unwrap().len())
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the absolute path of the stylesheet imported from the directory.
+    ///
+    /// Specifiers which cannot be resolved are relative to the directory.
+    pub fn resolve(&self, specifier: &str, dir: &Path) -> PathBuf {
+        if specifier.starts_with('.') || Path::new(specifier).has_root() {
+            return normalize(&dir.join(specifier));
+        }
+
+        // @ui/Button.module.css
+        for (alias, target) in &self.aliases {
+            if specifier == alias {
+                return target.clone();
+            }
+
+            if let Some(rest) = specifier
+                .strip_prefix(alias.as_str())
+                .and_then(|rest| rest.strip_prefix('/'))
+            {
+                return normalize(&target.join(rest));
+            }
+        }
+
+        // @theme/* → src/theme/*
+        for (pattern, targets) in &self.paths {
+            let Some(star) = match_pattern(pattern, specifier) else {
+                continue;
+            };
+
+            let candidates: Vec<_> = targets
+                .iter()
+                .map(|target| normalize(&self.paths_base.join(target.replacen('*', star, 1))))
+                .collect();
+
+            if let Some(path) = candidates
+                .iter()
+                .find(|path| path.is_file())
+                .or(candidates.first())
+            {
+                return path.clone();
+            }
+        }
+
+        // ~ is the legacy prefix of css-loader for modules
+        let bare = specifier.strip_prefix('~').unwrap_or(specifier);
+
+        if let Some(path) = self
+            .base_url
+            .as_ref()
+            .map(|base_url| normalize(&base_url.join(bare)))
+            .filter(|path| path.is_file())
+        {
+            return path;
+        }
+
+        match resolve_package(bare, dir) {
+            Ok(Some(path)) => return path,
+            Ok(None) => {}
+            Err(error) => diagnostics::report(
+                &self.config,
+                Kind::UnexportedSubpath,
+                DUMMY_SP,
+                &format!(
+                    "{}, the path is resolved relative to the importing file",
+                    error
+                ),
+            ),
+        }
+
+        normalize(&dir.join(specifier))
+    }
+}
+
+/// Resolves `package/subpath` in `node_modules` of the directory or its
+/// ancestors honoring `exports` of the package.
+///
+/// Returns `None` if there is no such package and an error if the package
+/// does not export the subpath.
+fn resolve_package(specifier: &str, dir: &Path) -> Result<Option<PathBuf>, String> {
+    let Some((name, subpath)) = split_package(specifier) else {
+        return Ok(None);
+    };
+
+    for ancestor in dir.ancestors() {
+        let root = ancestor.join("node_modules").join(name);
+
+        if !root.is_dir() {
+            continue;
+        }
+
+        let exports = fs::read_to_string(root.join("package.json"))
+            .ok()
+            .and_then(|manifest| serde_json::from_str::<Value>(&manifest).ok())
+            .and_then(|mut manifest| manifest.get_mut("exports").map(Value::take));
+
+        let Some(exports) = exports else {
+            return Ok(Some(normalize(&root.join(subpath.trim_start_matches('/')))));
+        };
+
+        return match export_target(&exports, &format!(".{subpath}")) {
+            Some(target) => Ok(Some(normalize(&root.join(target)))),
+            None => Err(format!("Package {} does not export \".{}\"", name, subpath)),
+        };
+    }
+
+    Ok(None)
+}
+
+/// Returns the path of the config tsconfig `extends` like `./tsconfig.base`
+/// or `@tsconfig/strictest/tsconfig.json` in `node_modules`.
+fn extends_path(base: &str, dir: &Path) -> Option<PathBuf> {
+    let candidates: Vec<_> = if base.starts_with('.') || Path::new(base).has_root() {
+        vec![dir.join(base)]
+    } else {
+        dir.ancestors()
+            .map(|ancestor| ancestor.join("node_modules").join(base))
+            .collect()
+    };
+
+    candidates.into_iter().find_map(|path| {
+        let mut json = path.clone().into_os_string();
+        json.push(".json");
+
+        [
+            path.clone(),
+            PathBuf::from(json),
+            path.join("tsconfig.json"),
+        ]
+        .into_iter()
+        .find(|path| path.is_file())
+    })
+}
+
+/// Splits the specifier to the package name and the subpath with the leading
+/// slash.
+///
+/// `@scope/kit/Card.module.css` → `("@scope/kit", "/Card.module.css")`
+fn split_package(specifier: &str) -> Option<(&str, &str)> {
+    let name_end = match specifier.strip_prefix('@') {
+        Some(scoped) => {
+            let scope_end = scoped.find('/')? + 2;
+
+            specifier[scope_end..]
+                .find('/')
+                .map_or(specifier.len(), |end| scope_end + end)
+        }
+        None => specifier.find('/').unwrap_or(specifier.len()),
+    };
+
+    Some(specifier.split_at(name_end))
+}
+
+/// Returns the target of the subpath like `./Card.module.css` in package
+/// `exports`, `None` if the package does not export it.
+fn export_target(exports: &Value, subpath: &str) -> Option<String> {
+    let subpaths = match exports {
+        Value::Object(map) if map.keys().all(|key| key.starts_with('.')) => map,
+        // "exports": "./index.css" is "exports": { ".": "./index.css" }
+        _ if subpath == "." => return conditional_target(exports, ""),
+        _ => return None,
+    };
+
+    if let Some(target) = subpaths.get(subpath) {
+        return conditional_target(target, "");
+    }
+
+    // "./*.module.css": "./dist/*.module.css"
+    subpaths
+        .iter()
+        .filter_map(|(pattern, target)| Some((pattern, match_pattern(pattern, subpath)?, target)))
+        .filter(|(pattern, _, _)| pattern.contains('*'))
+        .max_by_key(|(pattern, _, _)| pattern.split('*').next().unwrap().len())
+        .and_then(|(_, star, target)| conditional_target(target, star))
+}
+
+/// Returns the target of the first matching condition with `*` replaced.
+fn conditional_target(target: &Value, star: &str) -> Option<String> {
+    match target {
+        Value::String(target) => Some(target.replace('*', star)),
+        Value::Array(targets) => targets
+            .iter()
+            .find_map(|target| conditional_target(target, star)),
+        Value::Object(conditions) => conditions
+            .iter()
+            .filter(|(condition, _)| CONDITIONS.contains(&condition.as_str()))
+            .find_map(|(_, target)| conditional_target(target, star)),
+        _ => None,
+    }
+}
+
+/// Returns what `*` of the pattern matches in the specifier, an empty string
+/// for patterns without `*`.
+fn match_pattern<'a>(pattern: &str, specifier: &'a str) -> Option<&'a str> {
+    let Some((prefix, suffix)) = pattern.split_once('*') else {
+        return (pattern == specifier).then_some("");
+    };
+
+    if specifier.len() < prefix.len() + suffix.len() {
+        return None;
+    }
+
+    specifier
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.strip_suffix(suffix))
+}
+
+/// Removes `..` and `.` from the absolute path.
+fn normalize(path: &Path) -> PathBuf {
+    path.absolutize().unwrap().to_path_buf()
+}
+
+/// Removes comments and trailing commas tsconfig allows.
+fn strip_comments(source: &str) -> String {
+    let mut json = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                json.push(c);
+
+                while let Some(c) = chars.next() {
+                    json.push(c);
+
+                    match c {
+                        '\\' => json.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|&c| c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+
+                while let Some(c) = chars.next() {
+                    if c == '*' && chars.next_if_eq(&'/').is_some() {
+                        break;
+                    }
+                }
+            }
+            '}' | ']' => {
+                let trimmed = json.trim_end().len();
+
+                if json[..trimmed].ends_with(',') {
+                    json.truncate(trimmed - 1);
+                }
+
+                json.push(c);
+            }
+            _ => json.push(c),
+        }
+    }
+
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export_target, split_package, strip_comments};
+
+    #[test]
+    fn tsconfig_comments() {
+        let tsconfig = strip_comments(
+            r#"{
+  // Paths
+  "compilerOptions": { /* base */ "baseUrl": "./src/**", "paths": { "@/*": ["*",], }, },
+}"#,
+        );
+
+        let tsconfig: serde_json::Value = serde_json::from_str(&tsconfig).unwrap();
+
+        assert_eq!(tsconfig["compilerOptions"]["baseUrl"], "./src/**");
+        assert_eq!(tsconfig["compilerOptions"]["paths"]["@/*"][0], "*");
+    }
+
+    #[test]
+    fn package_names() {
+        assert_eq!(split_package("kit"), Some(("kit", "")));
+        assert_eq!(
+            split_package("kit/Card.module.css"),
+            Some(("kit", "/Card.module.css"))
+        );
+        assert_eq!(
+            split_package("@scope/kit/dist/Card.module.css"),
+            Some(("@scope/kit", "/dist/Card.module.css"))
+        );
+        assert_eq!(split_package("@scope"), None);
+    }
+
+    #[test]
+    fn package_exports() {
+        let exports = serde_json::json!({
+            ".": { "types": "./index.d.ts", "style": "./index.css" },
+            "./*.module.css": { "style": "./dist/*.module.css", "default": "./src/*.module.css" },
+            "./internal/*": null,
+        });
+
+        assert_eq!(
+            export_target(&exports, "."),
+            Some("./index.css".to_string())
+        );
+        assert_eq!(
+            export_target(&exports, "./Card.module.css"),
+            Some("./dist/Card.module.css".to_string())
+        );
+        assert_eq!(export_target(&exports, "./internal/Card.module.css"), None);
+        assert_eq!(export_target(&exports, "./package.json"), None);
+        assert_eq!(
+            export_target(&serde_json::json!("./index.css"), "."),
+            Some("./index.css".to_string())
+        );
+    }
+}
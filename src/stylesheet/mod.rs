@@ -0,0 +1,631 @@
+mod parser;
+mod rewrite;
+mod symbols;
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use parser::{parse, Node};
+pub use rewrite::{rewrite, Scope};
+pub use symbols::SymbolKind;
+
+/// A value declared by the `@value` at-rule.
+#[derive(Debug, PartialEq)]
+pub enum Value {
+    /// `@value primary: #BF4040;`
+    Literal(String),
+
+    /// `@value primary from "./colors.css";`
+    Import { name: String, from: String },
+}
+
+/// Classes composed by the `composes` declaration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Composes {
+    pub names: Vec<String>,
+    pub from: ComposesFrom,
+}
+
+/// Where composed classes are declared.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComposesFrom {
+    /// `composes: a;`
+    Local,
+
+    /// `composes: a from global;`
+    Global,
+
+    /// `composes: a from "./other.css";` or `composes: a from other;` where
+    /// `other` is a `@value` with the path.
+    File(String),
+}
+
+/// What the plugin knows about a CSS Modules stylesheet.
+#[derive(Debug, Default)]
+pub struct Stylesheet {
+    /// Values of [ICSS](https://github.com/css-modules/icss#export) `:export` blocks.
+    pub exports: HashMap<String, String>,
+
+    /// Values declared by `@value`.
+    pub values: HashMap<String, Value>,
+
+    /// Scoped names declared by the stylesheet.
+    pub symbols: HashMap<String, SymbolKind>,
+
+    /// Classes used in `:global` context.
+    pub globals: HashSet<String>,
+
+    /// Classes composed by classes of the stylesheet.
+    pub composes: HashMap<String, Vec<Composes>>,
+}
+
+impl Stylesheet {
+    /// Reads the stylesheet from the disk.
+    ///
+    /// Returns `None` if the file cannot be read.
+    pub fn read(filepath: &Path) -> Option<Self> {
+        fs::read_to_string(filepath)
+            .ok()
+            .map(|source| Self::parse(&source))
+    }
+
+    /// Collects everything the plugin needs from the stylesheet source.
+    pub fn parse(source: &str) -> Self {
+        let mut stylesheet = Self::default();
+
+        stylesheet.collect(parse(source));
+
+        stylesheet
+    }
+
+    fn collect(&mut self, nodes: Vec<Node>) {
+        for node in nodes {
+            match node {
+                Node::Rule {
+                    selector: ":export",
+                    block,
+                    ..
+                } => {
+                    for node in block {
+                        if let Node::Declaration {
+                            property, value, ..
+                        } = node
+                        {
+                            self.exports.insert(property.to_string(), value.to_string());
+                        }
+                    }
+                }
+
+                Node::Rule {
+                    selector, block, ..
+                } => {
+                    let classes = symbols::classes(selector);
+
+                    for node in &block {
+                        if let Node::Declaration {
+                            property: "composes" | "compose-with",
+                            value,
+                            ..
+                        } = node
+                        {
+                            let Some(composes) = parse_composes(value) else {
+                                continue;
+                            };
+
+                            for class in &classes {
+                                self.composes
+                                    .entry(class.clone())
+                                    .or_default()
+                                    .push(composes.clone());
+                            }
+                        }
+                    }
+
+                    for class in classes {
+                        self.symbols.insert(class, SymbolKind::Class);
+                    }
+
+                    self.globals.extend(symbols::global_classes(selector));
+
+                    self.collect(block);
+                }
+
+                Node::AtRule {
+                    name: "value",
+                    prelude,
+                    block: None,
+                    ..
+                } => self.values.extend(parse_value(prelude)),
+
+                Node::AtRule { name, prelude, .. } if name.ends_with("keyframes") => {
+                    if let Some(name) = symbols::keyframes_name(prelude) {
+                        self.add_symbol(name, SymbolKind::Keyframes);
+                    }
+                }
+
+                Node::AtRule {
+                    name,
+                    prelude,
+                    block,
+                    ..
+                } => {
+                    match name {
+                        "container" => {
+                            if let Some(name) = symbols::container_query_name(prelude) {
+                                self.add_symbol(name, SymbolKind::Container);
+                            }
+                        }
+                        "property" if prelude.starts_with("--") => {
+                            self.add_symbol(prelude.to_string(), SymbolKind::CustomProperty);
+                        }
+                        _ => {}
+                    }
+
+                    if let Some(block) = block {
+                        self.collect(block);
+                    }
+                }
+
+                Node::Declaration {
+                    property, value, ..
+                } => {
+                    for (name, kind) in symbols::declared_names(property, value) {
+                        self.add_symbol(name, kind);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns all names the stylesheet exports to JS in alphabetical order.
+    pub fn names(&self) -> BTreeSet<&str> {
+        self.symbols
+            .keys()
+            .chain(self.values.keys())
+            .chain(self.exports.keys())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Returns `true` if the name is used only as a global class and thus is
+    /// not exported.
+    pub fn is_global_only(&self, name: &str) -> bool {
+        self.globals.contains(name)
+            && !self.symbols.contains_key(name)
+            && !self.values.contains_key(name)
+            && !self.exports.contains_key(name)
+    }
+
+    /// Adds the symbol unless the same name is used by a class.
+    fn add_symbol(&mut self, name: String, kind: SymbolKind) {
+        self.symbols.entry(name).or_insert(kind);
+    }
+}
+
+/// Parses the prelude of the `@value` at-rule like
+/// [postcss-modules-values](https://github.com/css-modules/postcss-modules-values) does.
+fn parse_value(prelude: &str) -> Vec<(String, Value)> {
+    if let Some((names, from)) = prelude.rsplit_once(" from ") {
+        let from = from.trim();
+        let names = names.trim();
+        let names = names
+            .strip_prefix('(')
+            .and_then(|names| names.strip_suffix(')'))
+            .unwrap_or(names);
+
+        let is_source = (from.len() > 1
+            && (from.starts_with('"') && from.ends_with('"')
+                || from.starts_with('\'') && from.ends_with('\'')))
+            || from.chars().all(is_ident_char);
+
+        if is_source && !names.contains(':') {
+            return names
+                .split(',')
+                .filter_map(|specifier| {
+                    let mut words = specifier.split_whitespace();
+
+                    match (words.next(), words.next(), words.next(), words.next()) {
+                        (Some(name), None, None, None) => Some((name, name)),
+                        (Some(name), Some("as"), Some(alias), None) => Some((name, alias)),
+                        _ => None,
+                    }
+                })
+                .map(|(name, alias)| {
+                    (
+                        alias.to_string(),
+                        Value::Import {
+                            name: name.to_string(),
+                            from: from.to_string(),
+                        },
+                    )
+                })
+                .collect();
+        }
+    }
+
+    let name_end = prelude
+        .find(|c: char| !is_ident_char(c))
+        .unwrap_or(prelude.len());
+
+    if name_end == 0 {
+        return vec![];
+    }
+
+    let (name, rest) = prelude.split_at(name_end);
+    let rest = rest.trim_start();
+    let value = rest.strip_prefix(':').unwrap_or(rest).trim();
+
+    vec![(name.to_string(), Value::Literal(value.to_string()))]
+}
+
+/// Parses the value of the `composes` declaration like
+/// [postcss-modules-extract-imports](https://github.com/css-modules/postcss-modules-extract-imports) does.
+fn parse_composes(value: &str) -> Option<Composes> {
+    let (names, from) = match value.rsplit_once(" from ") {
+        Some((names, "global")) => (names, ComposesFrom::Global),
+        Some((names, from)) => (names, ComposesFrom::File(from.trim().to_string())),
+        None => (value, ComposesFrom::Local),
+    };
+
+    let names: Vec<_> = names.split_whitespace().map(str::to_string).collect();
+
+    (!names.is_empty()).then_some(Composes { names, from })
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+/// `@value` references each other in a cycle.
+#[derive(Debug, PartialEq)]
+pub struct CycleError {
+    /// `name (file)` of every value in the cycle.
+    pub chain: Vec<String>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cyclic @value reference: {}", self.chain.join(" -> "))
+    }
+}
+
+/// Stylesheets read from the disk.
+#[derive(Default)]
+pub struct Stylesheets {
+    cache: HashMap<PathBuf, Option<Rc<Stylesheet>>>,
+}
+
+impl Stylesheets {
+    /// Returns the stylesheet, reading it on the first access.
+    pub fn get(&mut self, filepath: &Path) -> Option<Rc<Stylesheet>> {
+        self.cache
+            .entry(filepath.to_path_buf())
+            .or_insert_with(|| Stylesheet::read(filepath).map(Rc::new))
+            .clone()
+    }
+
+    /// Caches the stylesheet parsed from the source instead of reading it.
+    pub fn insert(&mut self, filepath: &Path, stylesheet: Stylesheet) -> Rc<Stylesheet> {
+        let stylesheet = Rc::new(stylesheet);
+
+        self.cache
+            .insert(filepath.to_path_buf(), Some(stylesheet.clone()));
+
+        stylesheet
+    }
+
+    /// Returns the value that should be inlined instead of a generated class
+    /// name, i. e. `:export` value or `@value` with all references resolved.
+    ///
+    /// `resolve` returns the path of the `@value` source imported from the
+    /// directory.
+    pub fn constant(
+        &mut self,
+        filepath: &Path,
+        name: &str,
+        resolve: &impl Fn(&str, &Path) -> PathBuf,
+    ) -> Result<Option<String>, CycleError> {
+        let Some(stylesheet) = self.get(filepath) else {
+            return Ok(None);
+        };
+
+        if let Some(value) = stylesheet.exports.get(name) {
+            return self
+                .replace_values(filepath, &stylesheet, value, resolve, &mut vec![])
+                .map(Some);
+        }
+
+        self.value(filepath, name, resolve, &mut vec![])
+    }
+
+    fn value(
+        &mut self,
+        filepath: &Path,
+        name: &str,
+        resolve: &impl Fn(&str, &Path) -> PathBuf,
+        stack: &mut Vec<(PathBuf, String)>,
+    ) -> Result<Option<String>, CycleError> {
+        let Some(stylesheet) = self.get(filepath) else {
+            return Ok(None);
+        };
+
+        let Some(value) = stylesheet.values.get(name) else {
+            return Ok(None);
+        };
+
+        let key = (filepath.to_path_buf(), name.to_string());
+
+        if let Some(start) = stack.iter().position(|entry| *entry == key) {
+            return Err(CycleError {
+                chain: stack[start..]
+                    .iter()
+                    .chain([&key])
+                    .map(|(filepath, name)| {
+                        format!(
+                            "{} ({})",
+                            name,
+                            filepath.file_name().unwrap_or_default().to_string_lossy()
+                        )
+                    })
+                    .collect(),
+            });
+        }
+
+        stack.push(key);
+
+        let result = match value {
+            Value::Literal(literal) => self
+                .replace_values(filepath, &stylesheet, literal, resolve, stack)
+                .map(Some),
+
+            Value::Import { name, from } => {
+                let source = match stylesheet.values.get(from) {
+                    // @value colors: "./colors.css";
+                    // @value primary from colors;
+                    Some(Value::Literal(source)) => source.as_str(),
+                    _ => from.as_str(),
+                };
+
+                let dir = filepath.parent().unwrap_or(Path::new(""));
+                let source = resolve(unquote(source), dir);
+
+                self.value(&source, name, resolve, stack)
+            }
+        };
+
+        stack.pop();
+
+        result
+    }
+
+    /// Replaces words of the text which are names of the values.
+    ///
+    /// Quoted strings are not words, `"./colors.css"` is kept as is.
+    fn replace_values(
+        &mut self,
+        filepath: &Path,
+        stylesheet: &Stylesheet,
+        text: &str,
+        resolve: &impl Fn(&str, &Path) -> PathBuf,
+        stack: &mut Vec<(PathBuf, String)>,
+    ) -> Result<String, CycleError> {
+        let mut result = String::with_capacity(text.len());
+        let mut word_start = None;
+        let mut quote = None;
+
+        for (index, c) in text.char_indices().chain([(text.len(), ' ')]) {
+            if let Some(open) = quote {
+                if index < text.len() {
+                    result.push(c);
+                }
+
+                if c == open {
+                    quote = None;
+                }
+
+                continue;
+            }
+
+            if is_ident_char(c) && index < text.len() {
+                word_start.get_or_insert(index);
+                continue;
+            }
+
+            if let Some(start) = word_start.take() {
+                let word = &text[start..index];
+
+                let value = if stylesheet.values.contains_key(word) {
+                    self.value(filepath, word, resolve, stack)?
+                } else {
+                    None
+                };
+
+                result.push_str(value.as_deref().unwrap_or(word));
+            }
+
+            if c == '"' || c == '\'' {
+                quote = Some(c);
+            }
+
+            if index < text.len() {
+                result.push(c);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Removes quotes around the string.
+pub fn unquote(source: &str) -> &str {
+    source
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .or_else(|| source.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')))
+        .unwrap_or(source)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::{collections::HashMap, env, fs, process};
+
+    use path_absolutize::*;
+
+    use super::{CycleError, Stylesheet, Stylesheets, SymbolKind, Value};
+
+    #[test]
+    fn icss_export() {
+        let stylesheet = Stylesheet::parse(
+            r#"
+            :export {
+              primaryColor: #f00;
+              fontStack: "Helvetica Neue", sans-serif;
+            }
+
+            .primaryColor { color: blue; }
+            "#,
+        );
+
+        assert_eq!(stylesheet.exports["primaryColor"], "#f00");
+        assert_eq!(
+            stylesheet.exports["fontStack"],
+            r#""Helvetica Neue", sans-serif"#
+        );
+    }
+
+    #[test]
+    fn symbols() {
+        let stylesheet = Stylesheet::parse(
+            r#"
+            .root { --brand: red; animation: fadeIn 1s; }
+            @media (min-width: 100px) {
+              .fadeIn { container-name: sidebar; }
+            }
+            @keyframes fadeIn { from { opacity: 0; } }
+            @keyframes spin { to { transform: rotate(1turn); } }
+            @container sidebar (min-width: 400px) {
+              .layout { grid-template-areas: "header"; }
+            }
+            "#,
+        );
+
+        assert_eq!(
+            stylesheet.symbols,
+            HashMap::from([
+                ("root".to_string(), SymbolKind::Class),
+                ("--brand".to_string(), SymbolKind::CustomProperty),
+                ("fadeIn".to_string(), SymbolKind::Class),
+                ("sidebar".to_string(), SymbolKind::Container),
+                ("spin".to_string(), SymbolKind::Keyframes),
+                ("layout".to_string(), SymbolKind::Class),
+                ("header".to_string(), SymbolKind::Grid),
+            ])
+        );
+    }
+
+    #[test]
+    fn global_classes() {
+        let stylesheet = Stylesheet::parse(
+            r#"
+            .root:global(.is-open) { }
+            :global .theme-dark .root, :global(.active) { }
+            .active { }
+            "#,
+        );
+
+        assert!(stylesheet.is_global_only("is-open"));
+        assert!(stylesheet.is_global_only("theme-dark"));
+        assert!(!stylesheet.is_global_only("active"));
+        assert!(!stylesheet.is_global_only("root"));
+    }
+
+    #[test]
+    fn value_definitions() {
+        let stylesheet = Stylesheet::parse(
+            r#"
+            @value primary: #BF4040;
+            @value small (max-width: 599px);
+            @value colors: "./colors.css";
+            @value secondary, tertiary as third from "./colors.css";
+            @value (accent) from colors;
+            "#,
+        );
+
+        assert_eq!(
+            stylesheet.values["primary"],
+            Value::Literal("#BF4040".to_string())
+        );
+        assert_eq!(
+            stylesheet.values["small"],
+            Value::Literal("(max-width: 599px)".to_string())
+        );
+        assert_eq!(
+            stylesheet.values["third"],
+            Value::Import {
+                name: "tertiary".to_string(),
+                from: "\"./colors.css\"".to_string()
+            }
+        );
+        assert_eq!(
+            stylesheet.values["accent"],
+            Value::Import {
+                name: "accent".to_string(),
+                from: "colors".to_string()
+            }
+        );
+        assert!(stylesheet.values.contains_key("secondary"));
+    }
+
+    #[test]
+    fn resolve_values_across_files() {
+        // Concurrent runs of the test write to their own directories
+        let dir = env::temp_dir()
+            .join("swc-plugin-css-modules")
+            .join(format!("resolve_values_across_files_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        fs::write(
+            dir.join("colors.css"),
+            "@value base: #BF4040; @value primary: base; @value loop from './cycle.css';",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("cycle.css"),
+            "@value loop from './colors.css'; @value border: 1px solid primary; @value primary from './colors.css'; @value colors: \"./colors.css\";",
+        )
+        .unwrap();
+
+        let mut stylesheets = Stylesheets::default();
+        let resolve =
+            |source: &str, dir: &Path| dir.join(source).absolutize().unwrap().to_path_buf();
+
+        assert_eq!(
+            stylesheets.constant(&dir.join("cycle.css"), "border", &resolve),
+            Ok(Some("1px solid #BF4040".to_string()))
+        );
+        assert_eq!(
+            stylesheets.constant(&dir.join("cycle.css"), "loop", &resolve),
+            Err(CycleError {
+                chain: vec![
+                    "loop (cycle.css)".to_string(),
+                    "loop (colors.css)".to_string(),
+                    "loop (cycle.css)".to_string()
+                ]
+            })
+        );
+        assert_eq!(
+            stylesheets.constant(&dir.join("cycle.css"), "colors", &resolve),
+            Ok(Some("\"./colors.css\"".to_string()))
+        );
+        assert_eq!(
+            stylesheets.constant(&dir.join("cycle.css"), "root", &resolve),
+            Ok(None)
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
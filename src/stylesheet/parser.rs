@@ -0,0 +1,253 @@
+/// A node of a stylesheet.
+///
+/// The parser only knows about the shape of CSS: at-rules, qualified rules and
+/// declarations. Meaning of selectors and values is up to the caller.
+#[derive(Debug)]
+pub enum Node<'a> {
+    /// `@name prelude;` or `@name prelude { ... }`
+    AtRule {
+        name: &'a str,
+        prelude: &'a str,
+        block: Option<Vec<Node<'a>>>,
+        /// The whole at-rule including its block or `;`.
+        source: &'a str,
+    },
+
+    /// `selector { ... }`
+    Rule {
+        selector: &'a str,
+        block: Vec<Node<'a>>,
+        /// The whole rule including its block.
+        source: &'a str,
+    },
+
+    /// `property: value`
+    Declaration {
+        property: &'a str,
+        value: &'a str,
+        /// The whole declaration including `;`.
+        source: &'a str,
+    },
+}
+
+/// Parses the stylesheet into a list of nodes.
+///
+/// The parser is forgiving: it never fails and skips everything it does not
+/// understand.
+pub fn parse(source: &str) -> Vec<Node<'_>> {
+    Parser { source, pos: 0 }.parse_block(true)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.source.as_bytes().get(self.pos).copied()
+    }
+
+    /// Parses nodes until the end of the current block or the end of the source.
+    ///
+    /// Unbalanced `}` are skipped on the top level.
+    fn parse_block(&mut self, top_level: bool) -> Vec<Node<'a>> {
+        let mut nodes = vec![];
+
+        loop {
+            self.skip_whitespace_and_comments();
+
+            match self.peek() {
+                None => break,
+                Some(b'}') => {
+                    self.pos += 1;
+
+                    if !top_level {
+                        break;
+                    }
+                }
+                Some(b'@') => {
+                    let start = self.pos;
+                    self.pos += 1;
+
+                    let name_start = self.pos;
+                    while self
+                        .peek()
+                        .is_some_and(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_')
+                    {
+                        self.pos += 1;
+                    }
+                    let name = &self.source[name_start..self.pos];
+
+                    let end = self.scan_until_delimiter();
+                    let prelude = self.source[self.pos..end].trim();
+
+                    self.pos = end;
+
+                    let block = match self.peek() {
+                        Some(b'{') => {
+                            self.pos += 1;
+                            Some(self.parse_block(false))
+                        }
+                        Some(b';') => {
+                            self.pos += 1;
+                            None
+                        }
+                        _ => None,
+                    };
+
+                    nodes.push(Node::AtRule {
+                        name,
+                        prelude,
+                        block,
+                        source: &self.source[start..self.pos],
+                    });
+                }
+                Some(_) => {
+                    let start = self.pos;
+                    let end = self.scan_until_delimiter();
+                    let text = self.source[start..end].trim();
+
+                    self.pos = end;
+
+                    match self.peek() {
+                        Some(b'{') => {
+                            self.pos += 1;
+
+                            let block = self.parse_block(false);
+
+                            nodes.push(Node::Rule {
+                                selector: text,
+                                block,
+                                source: &self.source[start..self.pos],
+                            });
+                        }
+                        delimiter => {
+                            if delimiter == Some(b';') {
+                                self.pos += 1;
+                            }
+
+                            if let Some((property, value)) = text.split_once(':') {
+                                nodes.push(Node::Declaration {
+                                    property: property.trim(),
+                                    value: value.trim(),
+                                    source: &self.source[start..self.pos],
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        nodes
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_ascii_whitespace() => self.pos += 1,
+                Some(b'/') if self.source[self.pos..].starts_with("/*") => {
+                    self.pos = self.comment_end(self.pos);
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Returns the position of the closest `;`, `{` or `}` on the current
+    /// nesting level, ignoring strings, comments and parentheses.
+    fn scan_until_delimiter(&self) -> usize {
+        let bytes = self.source.as_bytes();
+        let mut depth = 0usize;
+        let mut pos = self.pos;
+
+        while pos < bytes.len() {
+            match bytes[pos] {
+                b'\\' => pos += 1,
+                quote @ (b'"' | b'\'') => {
+                    pos += 1;
+                    while pos < bytes.len() && bytes[pos] != quote {
+                        if bytes[pos] == b'\\' {
+                            pos += 1;
+                        }
+                        pos += 1;
+                    }
+                }
+                b'/' if bytes.get(pos + 1) == Some(&b'*') => {
+                    pos = self.comment_end(pos) - 1;
+                }
+                b'(' | b'[' => depth += 1,
+                b')' | b']' => depth = depth.saturating_sub(1),
+                b';' | b'{' | b'}' if depth == 0 => return pos,
+                _ => {}
+            }
+
+            pos += 1;
+        }
+
+        bytes.len()
+    }
+
+    /// Returns the position right after the comment starting at `start`.
+    fn comment_end(&self, start: usize) -> usize {
+        self.source[start + 2..]
+            .find("*/")
+            .map_or(self.source.len(), |end| start + 2 + end + 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Node};
+
+    #[test]
+    fn nested_blocks() {
+        let nodes = parse(
+            r#"
+            /* comment; { } */
+            @import url("a;b.css");
+            .a:hover { color: red; background: url(data:image/png;base64,AAA) }
+            @media (max-width: 100px) { .b { color: blue } }
+            "#,
+        );
+
+        assert_eq!(nodes.len(), 3);
+
+        assert!(matches!(
+            nodes[0],
+            Node::AtRule {
+                name: "import",
+                prelude: "url(\"a;b.css\")",
+                block: None,
+                source: "@import url(\"a;b.css\");"
+            }
+        ));
+
+        let Node::Rule {
+            selector, block, ..
+        } = &nodes[1]
+        else {
+            panic!("expected rule");
+        };
+        assert_eq!(*selector, ".a:hover");
+        assert!(matches!(
+            block[1],
+            Node::Declaration {
+                property: "background",
+                value: "url(data:image/png;base64,AAA)",
+                source: "background: url(data:image/png;base64,AAA) "
+            }
+        ));
+
+        let Node::AtRule {
+            name: "media",
+            block: Some(block),
+            ..
+        } = &nodes[2]
+        else {
+            panic!("expected @media");
+        };
+        assert!(matches!(block[0], Node::Rule { selector: ".b", .. }));
+    }
+}
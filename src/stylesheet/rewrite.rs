@@ -0,0 +1,550 @@
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::Path;
+
+use super::parser::{parse, Node};
+use super::symbols::{self, is_name_char, matching_paren, SymbolKind};
+use super::Stylesheet;
+
+/// Names the rewritten stylesheet uses instead of the original ones.
+pub trait Scope {
+    /// Returns the scoped name of the symbol or `None` to keep it as is.
+    fn scoped_name(&mut self, name: &str, filepath: &Path) -> Option<String>;
+
+    /// Returns the `@value` with all references resolved.
+    fn value(&mut self, name: &str, filepath: &Path) -> Option<String>;
+}
+
+/// Returns the stylesheet with scoped names, `:export` blocks and `@value`
+/// definitions removed, and values inlined, i. e. what
+/// [postcss-modules](https://github.com/madyankin/postcss-modules) outputs.
+///
+/// Everything else including formatting and comments is kept as is.
+pub fn rewrite(
+    source: &str,
+    filepath: &Path,
+    stylesheet: &Stylesheet,
+    scope: &mut impl Scope,
+) -> String {
+    let nodes = parse(source);
+
+    let mut declared = HashSet::new();
+    collect_declared(&nodes, &mut declared);
+
+    let mut rewriter = Rewriter {
+        source,
+        filepath,
+        stylesheet,
+        declared,
+        scope,
+        edits: vec![],
+    };
+
+    rewriter.nodes(&nodes, false);
+
+    let mut edits = rewriter.edits;
+    edits.sort_by_key(|(range, _)| range.start);
+
+    let mut result = String::with_capacity(source.len());
+    let mut pos = 0;
+
+    for (range, replacement) in edits {
+        if range.start < pos {
+            continue;
+        }
+
+        result.push_str(&source[pos..range.start]);
+        result.push_str(&replacement);
+        pos = range.end;
+    }
+
+    result.push_str(&source[pos..]);
+    result
+}
+
+/// Collects every name with every kind it is declared as.
+///
+/// Unlike [`Stylesheet::symbols`], a name declared both as a class and as
+/// `@keyframes` is kept for both kinds.
+fn collect_declared(nodes: &[Node], declared: &mut HashSet<(String, SymbolKind)>) {
+    for node in nodes {
+        match node {
+            Node::Rule {
+                selector, block, ..
+            } => {
+                for class in symbols::classes(selector) {
+                    declared.insert((class, SymbolKind::Class));
+                }
+
+                collect_declared(block, declared);
+            }
+
+            Node::AtRule {
+                name,
+                prelude,
+                block,
+                ..
+            } => {
+                if name.ends_with("keyframes") {
+                    if let Some(name) = symbols::keyframes_name(prelude) {
+                        declared.insert((name, SymbolKind::Keyframes));
+                    }
+                } else if *name == "container" {
+                    if let Some(name) = symbols::container_query_name(prelude) {
+                        declared.insert((name, SymbolKind::Container));
+                    }
+                }
+
+                if let Some(block) = block {
+                    collect_declared(block, declared);
+                }
+            }
+
+            Node::Declaration {
+                property, value, ..
+            } => {
+                declared.extend(symbols::declared_names(property, value));
+            }
+        }
+    }
+}
+
+struct Rewriter<'a, S> {
+    source: &'a str,
+    filepath: &'a Path,
+    stylesheet: &'a Stylesheet,
+    declared: HashSet<(String, SymbolKind)>,
+    scope: &'a mut S,
+    edits: Vec<(Range<usize>, String)>,
+}
+
+impl<S: Scope> Rewriter<'_, S> {
+    /// Returns the range of the slice of the source.
+    fn range(&self, slice: &str) -> Range<usize> {
+        let start = slice.as_ptr() as usize - self.source.as_ptr() as usize;
+
+        start..start + slice.len()
+    }
+
+    fn replace(&mut self, slice: &str, replacement: String) {
+        if slice != replacement {
+            self.edits.push((self.range(slice), replacement));
+        }
+    }
+
+    fn nodes(&mut self, nodes: &[Node], in_keyframes: bool) {
+        for node in nodes {
+            match node {
+                Node::Rule {
+                    selector, source, ..
+                } if *selector == ":export" => self.replace(source, String::new()),
+
+                Node::Rule {
+                    selector, block, ..
+                } => {
+                    // Keyframe selectors like `12.5%` are not classes
+                    if !in_keyframes {
+                        let scoped = self.selector(selector);
+                        self.replace(selector, scoped);
+                    }
+
+                    self.nodes(block, false);
+                }
+
+                Node::AtRule {
+                    name: "value",
+                    block: None,
+                    source,
+                    ..
+                } => self.replace(source, String::new()),
+
+                Node::AtRule {
+                    name,
+                    prelude,
+                    block,
+                    ..
+                } => {
+                    let scoped = if name.ends_with("keyframes") {
+                        self.keyframes_prelude(prelude)
+                    } else {
+                        self.words(prelude, Some(name), None)
+                    };
+                    self.replace(prelude, scoped);
+
+                    if let Some(block) = block {
+                        self.nodes(block, name.ends_with("keyframes"));
+                    }
+                }
+
+                // Composed classes are exported to JS only
+                Node::Declaration {
+                    property: "composes" | "compose-with",
+                    source,
+                    ..
+                } => self.replace(source, String::new()),
+
+                Node::Declaration {
+                    property, value, ..
+                } => {
+                    if property.starts_with("--") {
+                        if let Some(scoped) = self.scope.scoped_name(property, self.filepath) {
+                            self.replace(property, scoped);
+                        }
+                    }
+
+                    let scoped = self.words(value, None, Some(property));
+                    self.replace(value, scoped);
+                }
+            }
+        }
+    }
+
+    /// `.a :global(.b) .c` → `.scoped-a .b .scoped-c`
+    fn selector(&mut self, selector: &str) -> String {
+        let mut result = String::with_capacity(selector.len());
+        let mut global = false;
+        let mut chars = selector.char_indices().peekable();
+
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    result.push(c);
+                    if let Some((_, escaped)) = chars.next() {
+                        result.push(escaped);
+                    }
+                }
+                '"' | '\'' | '[' => {
+                    let end = if c == '[' { ']' } else { c };
+
+                    result.push(c);
+                    for (_, next) in chars.by_ref() {
+                        result.push(next);
+                        if next == end {
+                            break;
+                        }
+                    }
+                }
+                ',' => {
+                    global = false;
+                    result.push(c);
+                }
+                '.' if !global => {
+                    let start = index + 1;
+                    let mut end = start;
+
+                    while let Some(&(index, next)) = chars.peek() {
+                        if next == '\\' {
+                            chars.next();
+                            end = chars.next().map_or(index + 1, |(i, c)| i + c.len_utf8());
+                        } else if is_name_char(next) {
+                            chars.next();
+                            end = index + next.len_utf8();
+                        } else {
+                            break;
+                        }
+                    }
+
+                    let raw = &selector[start..end];
+                    let class = symbols::classes(&selector[index..end]).pop();
+
+                    result.push('.');
+                    match class.and_then(|class| self.scope.scoped_name(&class, self.filepath)) {
+                        Some(scoped) => result.push_str(&escape(&scoped)),
+                        None => result.push_str(raw),
+                    }
+                }
+                ':' if selector[index..].starts_with(":global")
+                    || selector[index..].starts_with(":local") =>
+                {
+                    let pseudo = if selector[index..].starts_with(":global") {
+                        ":global"
+                    } else {
+                        ":local"
+                    };
+                    let rest = &selector[index + pseudo.len()..];
+
+                    if rest.starts_with(|c: char| is_name_char(c)) {
+                        // :globalize or so
+                        result.push(c);
+                        continue;
+                    }
+
+                    for _ in 1..pseudo.len() {
+                        chars.next();
+                    }
+
+                    if rest.starts_with('(') {
+                        let inner_len = matching_paren(rest);
+                        let inner = &rest[1..inner_len];
+
+                        let scoped = if pseudo == ":global" {
+                            inner.to_string()
+                        } else {
+                            self.selector(inner)
+                        };
+                        result.push_str(&scoped);
+
+                        // Skip `(...)` and the closing paren
+                        for _ in rest[..(inner_len + 1).min(rest.len())].chars() {
+                            chars.next();
+                        }
+                    } else {
+                        // :global .a .b
+                        global = pseudo == ":global";
+
+                        if result.is_empty() || result.ends_with(char::is_whitespace) {
+                            while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+                        }
+                    }
+                }
+                _ => result.push(c),
+            }
+        }
+
+        result
+    }
+
+    /// `fadeIn` → `scoped-fadeIn`, `:global(fadeIn)` → `fadeIn`
+    fn keyframes_prelude(&mut self, prelude: &str) -> String {
+        for (pseudo, local) in [(":global(", false), (":local(", true)] {
+            if let Some(name) = prelude.strip_prefix(pseudo) {
+                let name = name.strip_suffix(')').unwrap_or(name).trim();
+
+                let scoped = if local {
+                    self.scope.scoped_name(name, self.filepath)
+                } else {
+                    None
+                };
+
+                return scoped.unwrap_or_else(|| name.to_string());
+            }
+        }
+
+        match symbols::keyframes_name(prelude) {
+            Some(name) if prelude == name => {
+                self.scope.scoped_name(&name, self.filepath).unwrap_or(name)
+            }
+            _ => prelude.to_string(),
+        }
+    }
+
+    /// Replaces words of the declaration value or the at-rule prelude which
+    /// are references to values or symbols of the stylesheet.
+    fn words(&mut self, text: &str, at_rule: Option<&str>, property: Option<&str>) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut chars = text.char_indices().peekable();
+
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    result.push(c);
+                    if let Some((_, escaped)) = chars.next() {
+                        result.push(escaped);
+                    }
+                }
+                '"' | '\'' => {
+                    let start = index + 1;
+                    let mut end = text.len();
+
+                    while let Some((index, next)) = chars.next() {
+                        if next == '\\' {
+                            chars.next();
+                        } else if next == c {
+                            end = index;
+                            break;
+                        }
+                    }
+
+                    let string = &text[start..end];
+
+                    result.push(c);
+                    match property {
+                        Some("grid-template-areas" | "grid-template" | "grid") => {
+                            let areas = string
+                                .split(' ')
+                                .map(|area| self.symbol(area, SymbolKind::Grid))
+                                .collect::<Vec<_>>()
+                                .join(" ");
+                            result.push_str(&areas);
+                        }
+                        _ => result.push_str(string),
+                    }
+                    if end < text.len() {
+                        result.push(c);
+                    }
+                }
+                _ if is_name_char(c) => {
+                    let start = index;
+                    let mut end = index + c.len_utf8();
+
+                    while let Some((index, next)) = chars.next_if(|(_, c)| is_name_char(*c)) {
+                        end = index + next.len_utf8();
+                    }
+
+                    let word = &text[start..end];
+
+                    // url(./a.png) is not a list of words
+                    if word == "url" && text[end..].starts_with('(') {
+                        result.push_str(word);
+                        for (_, next) in chars.by_ref() {
+                            result.push(next);
+                            if next == ')' {
+                                break;
+                            }
+                        }
+                        continue;
+                    }
+
+                    let replacement = self.word(word, at_rule, property);
+                    result.push_str(&replacement);
+                }
+                _ => result.push(c),
+            }
+        }
+
+        result
+    }
+
+    fn word(&mut self, word: &str, at_rule: Option<&str>, property: Option<&str>) -> String {
+        if self.stylesheet.values.contains_key(word) {
+            if let Some(value) = self.scope.value(word, self.filepath) {
+                return value;
+            }
+        }
+
+        if word.starts_with("--") {
+            return self
+                .scope
+                .scoped_name(word, self.filepath)
+                .unwrap_or_else(|| word.to_string());
+        }
+
+        let kind = match (at_rule, property) {
+            (Some("container"), _) => SymbolKind::Container,
+            (_, Some("animation" | "animation-name")) => SymbolKind::Keyframes,
+            (_, Some("container" | "container-name")) => SymbolKind::Container,
+            (_, Some(property)) if property.starts_with("grid") => {
+                // header-start is the line of `header` area
+                for suffix in ["-start", "-end"] {
+                    if let Some(area) = word.strip_suffix(suffix) {
+                        if self.is_declared(area, SymbolKind::Grid) {
+                            return self.symbol(area, SymbolKind::Grid) + suffix;
+                        }
+                    }
+                }
+
+                SymbolKind::Grid
+            }
+            _ => return word.to_string(),
+        };
+
+        self.symbol(word, kind)
+    }
+
+    fn is_declared(&self, name: &str, kind: SymbolKind) -> bool {
+        self.declared.contains(&(name.to_string(), kind))
+    }
+
+    /// Returns the scoped name if the name is declared as the kind.
+    fn symbol(&mut self, name: &str, kind: SymbolKind) -> String {
+        if !self.is_declared(name, kind) {
+            return name.to_string();
+        }
+
+        self.scope
+            .scoped_name(name, self.filepath)
+            .unwrap_or_else(|| name.to_string())
+    }
+}
+
+/// Escapes the name to use it as a CSS identifier.
+fn escape(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+
+    for (index, c) in name.chars().enumerate() {
+        if index == 0 && c.is_ascii_digit() {
+            result.push_str(&format!("\\{:x} ", c as u32));
+        } else if is_name_char(c) {
+            result.push(c);
+        } else {
+            result.push('\\');
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    use super::{rewrite, Scope};
+    use crate::stylesheet::Stylesheet;
+
+    struct Prefix(HashMap<&'static str, &'static str>);
+
+    impl Scope for Prefix {
+        fn scoped_name(&mut self, name: &str, _: &Path) -> Option<String> {
+            Some(match name.strip_prefix("--") {
+                Some(name) => format!("--x-{name}"),
+                None => format!("x-{name}"),
+            })
+        }
+
+        fn value(&mut self, name: &str, _: &Path) -> Option<String> {
+            self.0.get(name).map(|value| value.to_string())
+        }
+    }
+
+    fn scoped(source: &str) -> String {
+        rewrite(
+            source,
+            Path::new("/a.css"),
+            &Stylesheet::parse(source),
+            &mut Prefix(HashMap::from([("small", "(max-width: 599px)")])),
+        )
+    }
+
+    #[test]
+    fn selectors() {
+        assert_eq!(
+            scoped(".a.b > .c:not(.d), :global(.e) .f, :global .g .h, a[href$=\".i\"] { }"),
+            ".x-a.x-b > .x-c:not(.x-d), .e .x-f, .g .h, a[href$=\".i\"] { }"
+        );
+        assert_eq!(scoped(".sm\\:flex { }"), ".x-sm\\:flex { }");
+    }
+
+    #[test]
+    fn composes() {
+        assert_eq!(
+            scoped(".a { composes: b c from \"./b.css\"; color: red; compose-with: d }"),
+            ".x-a {  color: red; }"
+        );
+    }
+
+    #[test]
+    fn symbols_and_values() {
+        assert_eq!(
+            scoped(
+                r#"
+@value small: (max-width: 599px);
+:export { a: b; }
+@media small { .a { animation: fadeIn 1s linear; color: var(--brand); } }
+@keyframes fadeIn { 12.5% { opacity: 0; } }
+@keyframes :global(spin) { }
+.linear { --brand: red; grid-template-areas: "head ." "main main"; grid-row: head-start; }
+"#
+            ),
+            r#"
+
+
+@media (max-width: 599px) { .x-a { animation: x-fadeIn 1s linear; color: var(--x-brand); } }
+@keyframes x-fadeIn { 12.5% { opacity: 0; } }
+@keyframes spin { }
+.x-linear { --x-brand: red; grid-template-areas: "x-head ." "x-main x-main"; grid-row: x-head-start; }
+"#
+        );
+    }
+}
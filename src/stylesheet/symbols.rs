@@ -0,0 +1,265 @@
+/// A kind of the name a stylesheet exports to JS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    /// `.root {}`
+    Class,
+
+    /// `@keyframes fadeIn {}`
+    Keyframes,
+
+    /// `--brand: red;`
+    CustomProperty,
+
+    /// `grid-template-areas: "header"` or `grid-template-columns: [content-start]`
+    Grid,
+
+    /// `container-name: sidebar;`
+    Container,
+}
+
+/// Returns local class names used in the selector.
+///
+/// Classes inside `:global(...)` or after `:global` are not scoped and thus
+/// not exported, see [`global_classes`].
+pub fn classes(selector: &str) -> Vec<String> {
+    let mut classes = Classes::default();
+
+    scan_classes(selector, false, &mut classes);
+
+    classes.local
+}
+
+/// Returns class names used in `:global` context of the selector.
+pub fn global_classes(selector: &str) -> Vec<String> {
+    let mut classes = Classes::default();
+
+    scan_classes(selector, false, &mut classes);
+
+    classes.global
+}
+
+#[derive(Default)]
+struct Classes {
+    local: Vec<String>,
+    global: Vec<String>,
+}
+
+fn scan_classes(selector: &str, mut global: bool, classes: &mut Classes) {
+    let initial = global;
+    let mut chars = selector.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '"' | '\'' | '[' => {
+                let end = if c == '[' { ']' } else { c };
+
+                for (_, next) in chars.by_ref() {
+                    if next == end {
+                        break;
+                    }
+                }
+            }
+            ',' => global = initial,
+            ':' => {
+                let rest = &selector[index + 1..];
+                let pseudo = ["global", "local"].into_iter().find(|pseudo| {
+                    rest.starts_with(pseudo) && !rest[pseudo.len()..].starts_with(is_name_char)
+                });
+
+                let Some(pseudo) = pseudo else {
+                    continue;
+                };
+
+                let rest = &rest[pseudo.len()..];
+
+                for _ in 0..pseudo.len() {
+                    chars.next();
+                }
+
+                if rest.starts_with('(') {
+                    // :global(.a .b)
+                    let end = matching_paren(rest);
+
+                    scan_classes(&rest[1..end], pseudo == "global", classes);
+
+                    for _ in rest[..(end + 1).min(rest.len())].chars() {
+                        chars.next();
+                    }
+                } else {
+                    // :global .a .b
+                    global = pseudo == "global";
+                }
+            }
+            '.' => {
+                let mut ident = String::new();
+
+                while let Some(&(_, next)) = chars.peek() {
+                    match next {
+                        '\\' => {
+                            chars.next();
+
+                            if let Some((_, escaped)) = chars.next() {
+                                ident.push(escaped);
+                            }
+                        }
+                        next if is_name_char(next) => {
+                            ident.push(next);
+                            chars.next();
+                        }
+                        _ => break,
+                    }
+                }
+
+                if !ident.is_empty() && !ident.starts_with(|c: char| c.is_ascii_digit()) {
+                    if global {
+                        classes.global.push(ident);
+                    } else {
+                        classes.local.push(ident);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the index of the `)` closing the paren at the start of the text.
+pub fn matching_paren(text: &str) -> usize {
+    let mut depth = 0;
+
+    for (index, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return index;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    text.len()
+}
+
+pub fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()
+}
+
+/// Returns the name of `@keyframes` from its prelude.
+pub fn keyframes_name(prelude: &str) -> Option<String> {
+    let name = prelude.trim().trim_matches(|c| c == '"' || c == '\'');
+
+    (!name.is_empty() && name.chars().all(is_name_char)).then(|| name.to_string())
+}
+
+/// Returns the name of `@container` from its prelude.
+pub fn container_query_name(prelude: &str) -> Option<String> {
+    let name = prelude.split_whitespace().next()?;
+
+    (!matches!(name, "not" | "and" | "or") && name.chars().all(is_name_char))
+        .then(|| name.to_string())
+}
+
+/// Returns names declared by the declaration.
+pub fn declared_names(property: &str, value: &str) -> Vec<(String, SymbolKind)> {
+    let mut names = vec![];
+
+    if property.starts_with("--") {
+        names.push((property.to_string(), SymbolKind::CustomProperty));
+    }
+
+    match property {
+        "grid-template-areas" | "grid-template" | "grid" => {
+            for area in quoted_strings(value) {
+                for name in area.split_whitespace() {
+                    if name.chars().all(is_name_char) {
+                        names.push((name.to_string(), SymbolKind::Grid));
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+
+    match property {
+        "grid-template-columns" | "grid-template-rows" | "grid-template" | "grid" => {
+            for line in bracketed(value) {
+                for name in line.split_whitespace() {
+                    names.push((name.to_string(), SymbolKind::Grid));
+                }
+            }
+        }
+
+        "container-name" | "container" => {
+            let value = value.split('/').next().unwrap_or_default();
+
+            for name in value.split_whitespace() {
+                if name != "none" && name.chars().all(is_name_char) {
+                    names.push((name.to_string(), SymbolKind::Container));
+                }
+            }
+        }
+
+        _ => {}
+    }
+
+    names
+}
+
+fn quoted_strings(value: &str) -> Vec<&str> {
+    value.split(['"', '\'']).skip(1).step_by(2).collect()
+}
+
+fn bracketed(value: &str) -> Vec<&str> {
+    value
+        .split('[')
+        .skip(1)
+        .filter_map(|part| part.split_once(']').map(|(names, _)| names))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{classes, declared_names, global_classes, SymbolKind};
+
+    #[test]
+    fn classes_in_selector() {
+        assert_eq!(
+            classes(r#".a.b > .c:not(.d) [data-x=".e"] .sm\:flex, .f--g::before"#),
+            vec!["a", "b", "c", "d", "sm:flex", "f--g"]
+        );
+    }
+
+    #[test]
+    fn global_classes_in_selector() {
+        let selector = ".a :global(.b .c:not(:local(.d))) .e, :global .f :local(.g), .h";
+
+        assert_eq!(classes(selector), vec!["a", "d", "e", "g", "h"]);
+        assert_eq!(global_classes(selector), vec!["b", "c", "f"]);
+    }
+
+    #[test]
+    fn grid_names() {
+        assert_eq!(
+            declared_names("grid-template-areas", r#""header header" ". main""#),
+            vec![
+                ("header".to_string(), SymbolKind::Grid),
+                ("header".to_string(), SymbolKind::Grid),
+                ("main".to_string(), SymbolKind::Grid),
+            ]
+        );
+        assert_eq!(
+            declared_names("grid-template-columns", "[full-start] 1fr [full-end]"),
+            vec![
+                ("full-start".to_string(), SymbolKind::Grid),
+                ("full-end".to_string(), SymbolKind::Grid),
+            ]
+        );
+    }
+}
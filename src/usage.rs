@@ -0,0 +1,112 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Locals of stylesheets accessed from JS.
+///
+/// The usage file has a line per access: the stylesheet path relative to the
+/// root and the local separated by a tab. Every transformed file appends its
+/// lines, so files written by parallel builds can be simply concatenated.
+///
+/// [`Usage::ALL`] instead of the local means the file accesses the stylesheet
+/// in a way the plugin cannot follow, e.g. passes it to a function, so every
+/// local may be used.
+#[derive(Debug, Default, PartialEq)]
+pub struct Usage {
+    locals: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Usage {
+    /// The local meaning every local of the stylesheet is used.
+    pub const ALL: &'static str = "*";
+
+    /// Reads the usage file, a missing file means nothing is used.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(source) => Ok(Self::parse(&source)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn parse(source: &str) -> Self {
+        let mut usage = Self::default();
+
+        for (stylesheet, local) in source.lines().filter_map(|line| line.split_once('\t')) {
+            usage.add(stylesheet, local);
+        }
+
+        usage
+    }
+
+    pub fn add(&mut self, stylesheet: &str, local: &str) {
+        self.locals
+            .entry(stylesheet.to_string())
+            .or_default()
+            .insert(local.to_string());
+    }
+
+    /// Returns locals of the stylesheet accessed from JS.
+    pub fn locals(&self, stylesheet: &str) -> Option<&BTreeSet<String>> {
+        self.locals.get(stylesheet)
+    }
+
+    /// Returns `true` if the local of the stylesheet is accessed from JS.
+    pub fn is_used(&self, stylesheet: &str, local: &str) -> bool {
+        self.locals(stylesheet)
+            .is_some_and(|locals| locals.contains(local) || locals.contains(Self::ALL))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.locals.is_empty()
+    }
+
+    /// Appends the usage to the file.
+    ///
+    /// The file is opened with `O_APPEND` and every line is a separate write,
+    /// so lines of files transformed in parallel are not interleaved.
+    pub fn append_to(&self, path: &Path) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        for (stylesheet, locals) in &self.locals {
+            for local in locals {
+                file.write_all(format!("{stylesheet}\t{local}\n").as_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Usage;
+
+    #[test]
+    fn merge_lines() {
+        let usage =
+            Usage::parse("src/a.css\troot\nsrc/b.css\ticon\nsrc/a.css\troot\nsrc/a.css\ttitle\n");
+
+        assert_eq!(
+            usage
+                .locals("src/a.css")
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            ["root", "title"]
+        );
+        assert_eq!(usage.locals("src/b.css").unwrap().len(), 1);
+        assert_eq!(usage.locals("src/c.css"), None);
+    }
+
+    #[test]
+    fn all_used() {
+        let usage = Usage::parse("src/a.css\troot\nsrc/b.css\t*\n");
+
+        assert!(usage.is_used("src/a.css", "root"));
+        assert!(!usage.is_used("src/a.css", "title"));
+        assert!(usage.is_used("src/b.css", "title"));
+        assert!(!usage.is_used("src/c.css", "root"));
+    }
+}
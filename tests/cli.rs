@@ -0,0 +1,130 @@
+use std::{env, fs, process::Command};
+
+fn cli(args: &[&str]) -> (i32, String) {
+    cli_in("tests/fixture/values", args)
+}
+
+fn cli_in(cwd: &str, args: &[&str]) -> (i32, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_swc-css-modules"))
+        .args(["--cwd", cwd])
+        .args(args)
+        .output()
+        .unwrap();
+
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn name() {
+    assert_eq!(
+        cli(&[
+            "--config",
+            r#"{"generate_scoped_name": "[name]__[local]"}"#,
+            "name",
+            "Button.module.css",
+            "root",
+            "border",
+        ]),
+        (
+            0,
+            "root\tButton-module__root\nborder\t1px solid #BF4040\n".to_string()
+        )
+    );
+}
+
+#[test]
+fn relative_cwd() {
+    let args = [
+        "--config",
+        r#"{"generate_scoped_name": "[local]_[hash:base64:5]"}"#,
+        "name",
+        "Button.module.css",
+        "root",
+    ];
+    let absolute = env::current_dir().unwrap().join("tests/fixture/values");
+
+    assert_eq!(cli(&args), (0, "root\troot_27laX\n".to_string()));
+    assert_eq!(cli_in(absolute.to_str().unwrap(), &args), cli(&args));
+}
+
+#[test]
+fn manifest() {
+    let (code, stdout) = cli(&["--config", "config.json", "manifest", "Button.module.css"]);
+    let manifest: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+
+    assert_eq!(code, 0);
+    assert_eq!(manifest["root"], "Button-module__root");
+    assert_eq!(manifest["brand"], "#BF4040");
+}
+
+#[test]
+fn diff() {
+    assert_eq!(
+        cli(&[
+            "diff",
+            "config.json",
+            r#"{"generate_scoped_name": "[local]"}"#,
+            "Button.module.css",
+        ]),
+        (
+            1,
+            "Button.module.css\troot\tButton-module__root\troot\n".to_string()
+        )
+    );
+    assert_eq!(
+        cli(&["diff", "config.json", "config.json", "Button.module.css"]),
+        (0, String::new())
+    );
+}
+
+#[test]
+fn unreadable_stylesheet() {
+    assert_eq!(cli(&["manifest", "Missing.module.css"]).0, 2);
+}
+
+#[test]
+fn css() {
+    assert_eq!(
+        cli(&["--config", "config.json", "css", "Button.module.css"]),
+        (
+            0,
+            "\n\n\n\n.Button-module__root {\n  color: #BF4040;\n  border: 1px solid #BF4040;\n}\n"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn unused() {
+    let usage_file = env::temp_dir().join("swc-plugin-css-modules-usage.tsv");
+    fs::write(
+        &usage_file,
+        "Card.module.css\ttitle\nOther.module.css\troot\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        cli_in(
+            "tests/fixture/locals_convention",
+            &["unused", usage_file.to_str().unwrap(), "Card.module.css"],
+        ),
+        (
+            1,
+            "Card.module.css\tcard-header\nCard.module.css\tcard-header--active\n".to_string()
+        )
+    );
+
+    // The stylesheet escaped, so every class may be used
+    fs::write(&usage_file, "Card.module.css\ttitle\nCard.module.css\t*\n").unwrap();
+
+    assert_eq!(
+        cli_in(
+            "tests/fixture/locals_convention",
+            &["unused", usage_file.to_str().unwrap(), "Card.module.css"],
+        ),
+        (0, String::new())
+    );
+}
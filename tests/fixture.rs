@@ -1,22 +1,123 @@
-use std::{env, fs, path::PathBuf};
+use std::sync::{Arc, Mutex};
+use std::{env, fs, path::Path, path::PathBuf};
 
+use serde::Deserialize;
+use swc_core::common::errors::{
+    Diagnostic, DiagnosticBuilder, Emitter, Handler, HandlerFlags, HANDLER,
+};
+use swc_core::common::Mark;
 use swc_core::ecma::{
-    parser::{EsSyntax, Syntax},
-    transforms::testing::test_fixture,
+    ast::{fn_pass, Pass, Program},
+    parser::{EsSyntax, Syntax, TsSyntax},
+    transforms::base::resolver,
+    transforms::testing::{test_fixture, FixtureTestConfig},
     visit::visit_mut_pass,
 };
 use swc_plugin_css_modules::{Config, Injector};
+use testing::NormalizedOutput;
+
+/// Metadata of the file swc passes to the plugin.
+#[derive(Deserialize)]
+struct Metadata {
+    cwd: String,
+    filename: String,
+}
+
+/// Picks the syntax by the extension of the input.
+fn syntax(input: &Path) -> Syntax {
+    match input.extension().and_then(|ext| ext.to_str()) {
+        Some("ts") => Syntax::Typescript(TsSyntax::default()),
+        Some("tsx") => Syntax::Typescript(TsSyntax {
+            tsx: true,
+            ..Default::default()
+        }),
+        _ => Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+        }),
+    }
+}
+
+/// Collects diagnostics of the pass.
+#[derive(Clone, Default)]
+struct Collector(Arc<Mutex<Vec<Diagnostic>>>);
+
+impl Emitter for Collector {
+    fn emit(&mut self, db: &mut DiagnosticBuilder<'_>) {
+        self.0.lock().unwrap().push((**db).clone());
+    }
+}
+
+/// Runs the pass with a handler emitting warnings and forwards its
+/// diagnostics to the handler of the test, which drops warnings.
+struct WithWarnings<P>(P);
+
+impl<P: Pass> Pass for WithWarnings<P> {
+    fn process(&mut self, program: &mut Program) {
+        let collector = Collector::default();
+        let handler = Handler::with_emitter_and_flags(
+            Box::new(collector.clone()),
+            HandlerFlags {
+                can_emit_warnings: true,
+                ..Default::default()
+            },
+        );
+
+        HANDLER.set(&handler, || self.0.process(program));
 
-fn syntax() -> Syntax {
-    Syntax::Es(EsSyntax {
-        jsx: true,
-        ..Default::default()
-    })
+        HANDLER.with(|test_handler| {
+            for diagnostic in collector.0.lock().unwrap().drain(..) {
+                DiagnosticBuilder::new_diagnostic(test_handler, diagnostic).emit();
+            }
+        });
+    }
 }
 
-#[testing::fixture("tests/fixture/**/input.js")]
+/// Copies the fixture to a temporary directory, so files the plugin writes
+/// next to stylesheets do not end up in the source tree.
+///
+/// Written `*.d.ts` files and the usage file of the fixture are snapshots and
+/// are not copied.
+fn copy_to_temp(dir: &Path, usage_file: Option<&str>) -> PathBuf {
+    let name = dir.strip_prefix(env::current_dir().unwrap()).unwrap_or(dir);
+    let temp = env::temp_dir()
+        .join("swc-plugin-css-modules")
+        .join(name.to_string_lossy().replace(['/', '\\'], "_"));
+
+    let _ = fs::remove_dir_all(&temp);
+    fs::create_dir_all(&temp).unwrap();
+
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+
+        let snapshot = path.to_string_lossy().ends_with(".d.ts")
+            || usage_file.is_some_and(|usage_file| path.ends_with(usage_file));
+
+        if path.is_file() && !snapshot {
+            fs::copy(&path, temp.join(path.file_name().unwrap())).unwrap();
+        }
+    }
+
+    temp.canonicalize().unwrap()
+}
+
+/// Every fixture directory contains:
+///
+/// - `input.{js,jsx,ts,tsx}` and `config.json`
+/// - `output.{js,jsx,ts,tsx}` with expected code
+/// - `output.stderr` with expected diagnostics, if the plugin emits any
+/// - `*.d.ts` with expected declarations, if `emit_declarations` is enabled,
+///   the plugin writes them in a copy of the fixture
+/// - the usage file with expected accessed locals, if `usage_file` is set,
+///   the plugin writes it in a copy of the fixture
+/// - `metadata.json` with `cwd` and `filename` an entrypoint passes, if they
+///   differ from the absolute input path, `{root}` is the repository
+#[testing::fixture("tests/fixture/**/input.*")]
 fn fixture(input: PathBuf) {
-    let output = input.parent().unwrap().join("output.js");
+    let output = input
+        .with_file_name("output")
+        .with_extension(input.extension().unwrap());
+    let stderr = input.with_file_name("output.stderr");
     let config_path = input.parent().unwrap().join("config.json");
 
     let config_file = fs::File::open(config_path).expect("failed to open config");
@@ -24,18 +125,70 @@ fn fixture(input: PathBuf) {
     let config: Config = serde_json::from_reader(config_file).expect("invalid config");
 
     let cwd = env::current_dir().unwrap().canonicalize().unwrap();
+    let root = cwd.to_str().unwrap();
+
+    let (cwd, filename) = match fs::read_to_string(input.with_file_name("metadata.json")) {
+        Ok(metadata) => {
+            let metadata: Metadata = serde_json::from_str(&metadata).expect("invalid metadata");
+
+            (
+                metadata.cwd.replace("{root}", root),
+                metadata.filename.replace("{root}", root),
+            )
+        }
+        Err(_) => (root.to_string(), input.to_str().unwrap().to_string()),
+    };
+
+    let dir = input.parent().unwrap();
+    let temp = (config.emit_declarations || config.usage_file.is_some())
+        .then(|| copy_to_temp(dir, config.usage_file.as_deref()));
 
+    let (cwd, filename) = match &temp {
+        Some(temp) => (
+            temp.to_str().unwrap().to_string(),
+            temp.join(input.file_name().unwrap())
+                .to_str()
+                .unwrap()
+                .to_string(),
+        ),
+        None => (cwd, filename),
+    };
+
+    let syntax = syntax(&input);
+
+    // swc resolves bindings before plugins run
     test_fixture(
-        syntax(),
+        syntax,
         &|_| {
-            visit_mut_pass(Injector::new(
-                cwd.to_str().unwrap(),
-                input.to_str().unwrap(),
-                config.clone(),
-            ))
+            (
+                resolver(Mark::new(), Mark::new(), syntax.typescript()),
+                // Entrypoints validate the config after parsing it
+                WithWarnings((
+                    fn_pass(|_| config.validate()),
+                    visit_mut_pass(Injector::new(&cwd, &filename, config.clone())),
+                )),
+            )
         },
         &input,
         &output,
-        Default::default(),
+        FixtureTestConfig {
+            allow_error: stderr.exists(),
+            ..Default::default()
+        },
     );
+
+    let Some(temp) = temp else {
+        return;
+    };
+
+    for entry in fs::read_dir(&temp).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_str().unwrap();
+
+        if name.ends_with(".d.ts") || config.usage_file.as_deref() == Some(name) {
+            NormalizedOutput::from(fs::read_to_string(&path).unwrap())
+                .compare_to_file(dir.join(name))
+                .unwrap();
+        }
+    }
 }
@@ -0,0 +1,24 @@
+import classNames from "classnames/bind";
+import styles from "./Button.module.css";
+
+const cx = classNames.bind(styles);
+
+export const Button = ({ active, disabled, size }) => (
+  <button
+    className={cx("root", "is-open", { active, "is-disabled": disabled }, ["icon"], size)}
+  />
+);
+
+const label = classNames.bind(styles);
+
+export const Label = ({ active }) => <span className={label("icon", { active })} />;
+
+const title = classNames.bind(styles);
+
+export { title };
+
+export function Icon() {
+  return <i className={early("icon")} />;
+}
+
+const early = classNames.bind(styles);
@@ -0,0 +1,19 @@
+import classNames from "classnames/bind";
+import styles from "./Button.module.css";
+const cx = classNames.bind(styles);
+export const Button = ({ active, disabled, size })=><button className={cx("Button-module__root", "is-open", {
+        "Button-module__active": active,
+        "Button-module__is-disabled": disabled
+    }, [
+        "Button-module__icon"
+    ], size)}/>;
+const label = classNames;
+export const Label = ({ active })=><span className={label("Button-module__icon", {
+        "Button-module__active": active
+    })}/>;
+const title = classNames.bind(styles);
+export { title };
+export function Icon() {
+    return <i className={early("icon")}/>;
+}
+const early = classNames.bind(styles);
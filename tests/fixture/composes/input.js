@@ -0,0 +1,3 @@
+import styles from "./Card.module.css";
+
+render(styles.root, styles.title);
@@ -0,0 +1,2 @@
+import "./Card.module.css";
+render("Card-module__root Card-module__title Base-module__heading Base-module__text clearfix Base-module__bordered", "Card-module__title Base-module__heading Base-module__text");
@@ -0,0 +1,3 @@
+import styles from "./Card.module.css";
+
+const title = styles.title;
@@ -0,0 +1,2 @@
+import "./Card.module.css";
+const title = "Card-module__title Card-module__base Base-module__text";
@@ -0,0 +1,7 @@
+async function load() {
+  const styles = await import("./Page.module.css");
+
+  return import("./Page.module.css").then((m) => m.default.root);
+}
+
+import("./page.js");
@@ -0,0 +1,21 @@
+async function load() {
+  const styles = await import("./Page.module.css").then(() => ({
+    default: {
+      "page-title": "page_page-title",
+      root: "page_root",
+    },
+    root: "page_root",
+  }));
+
+  return import("./Page.module.css")
+    .then(() => ({
+      default: {
+        "page-title": "page_page-title",
+        root: "page_root",
+      },
+      root: "page_root",
+    }))
+    .then((m) => m.default.root);
+}
+
+import("./page.js");
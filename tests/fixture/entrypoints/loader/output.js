@@ -0,0 +1,3 @@
+import "../Card.module.css";
+
+const className = "root_2bf4d98b";
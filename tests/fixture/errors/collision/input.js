@@ -0,0 +1 @@
+import a from "./A.module.css"; import b from "./B.module.css"; use(a.root, b.root, a.root);
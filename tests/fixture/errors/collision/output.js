@@ -0,0 +1 @@
+import "./A.module.css"; import "./B.module.css"; use("app-root", "app-root", "app-root");
@@ -0,0 +1 @@
+import styles from "./Component.module.css"; const title = styles[prefix + "title"];
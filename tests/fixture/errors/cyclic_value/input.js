@@ -0,0 +1 @@
+import styles from "./Component.module.css"; const color = styles.loop;
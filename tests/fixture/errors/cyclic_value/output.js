@@ -0,0 +1 @@
+import "./Component.module.css"; const color = "loop";
@@ -0,0 +1 @@
+import styles, { title } from "./Card.module.css"; use(styles.root, styles, { title });
@@ -0,0 +1 @@
+import styles from "./Card.module.css"; use("root", styles, { title: "title" });
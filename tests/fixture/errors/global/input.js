@@ -0,0 +1 @@
+import styles from "./Modal.module.css"; el.className = styles["is-open"];
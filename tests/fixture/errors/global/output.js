@@ -0,0 +1 @@
+import "./Modal.module.css"; el.className = "is-open";
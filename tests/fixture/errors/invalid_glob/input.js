@@ -0,0 +1 @@
+import styles from "./Card.module.css"; const title = styles.title;
@@ -0,0 +1,2 @@
+import "./Card.module.css";
+const title = "title";
@@ -0,0 +1 @@
+import { cardHeader } from "./Card.module.css"; const header = cardHeader;
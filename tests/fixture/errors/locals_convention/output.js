@@ -0,0 +1,2 @@
+import "./Card.module.css";
+const header = "card-header";
@@ -0,0 +1,4 @@
+import card from "kit/Card.module.css";
+import internal from "kit/internal.css";
+
+const classes = [card.root, internal.root];
@@ -0,0 +1,6 @@
+import "kit/Card.module.css";
+import "kit/internal.css";
+const classes = [
+    "dist__root",
+    "kit__root"
+];
@@ -0,0 +1 @@
+import styles from "./Card.module.css"; use(styles.root, styles.missing, styles[key]);
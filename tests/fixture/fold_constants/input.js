@@ -0,0 +1,10 @@
+import clsx from "clsx";
+import styles from "./Card.module.css";
+
+export const card = `${styles.card} ${styles.active}`;
+
+export const title = clsx(styles.title, [styles.card], { [styles.active]: true, hidden: false });
+
+export const dynamic = clsx(styles.title, { [styles.active]: isActive });
+
+export const prefixed = `${prefix}-${styles.title}`;
@@ -0,0 +1,10 @@
+import clsx from "clsx";
+import "./Card.module.css";
+
+export const card = "Card-module__card Card-module__active";
+
+export const title = "Card-module__title Card-module__card Card-module__active";
+
+export const dynamic = clsx("Card-module__title", { ["Card-module__active"]: isActive });
+
+export const prefixed = `${prefix}-${"Card-module__title"}`;
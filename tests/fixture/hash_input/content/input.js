@@ -0,0 +1,5 @@
+import moved from "./a/Card.module.css";
+import copied from "./b/Card.module.css";
+import missing from "./missing/Card.module.css";
+
+const classes = [moved.root, copied.root, missing.root];
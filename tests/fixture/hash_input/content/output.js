@@ -0,0 +1,8 @@
+import "./a/Card.module.css";
+import "./b/Card.module.css";
+import "./missing/Card.module.css";
+const classes = [
+    "root_8722d72d",
+    "root_8722d72d",
+    "root_d8706598"
+];
@@ -0,0 +1,3 @@
+import styles from "@scope/ui/src/Button.module.css";
+
+const className = styles.root;
@@ -0,0 +1,3 @@
+import "@scope/ui/src/Button.module.css";
+
+const className = "root_a0c127f3";
@@ -0,0 +1,8 @@
+import theme, { fontStack } from "./Theme.module.css";
+
+const colors = {
+  primary: theme.primaryColor,
+  spacing: theme["spacing"],
+};
+
+render(theme.root, fontStack);
@@ -0,0 +1,8 @@
+import "./Theme.module.css";
+
+const colors = {
+  primary: "#f00",
+  spacing: "8px",
+};
+
+render("Theme-module__root", "Helvetica, sans-serif");
@@ -0,0 +1,4 @@
+import styles, { root, "foo-bar" as fooBar } from "./Card.module.css";
+import { default as card } from "./Card.module.css";
+
+use(styles.root, root, fooBar, card.title, card["foo-bar"]);
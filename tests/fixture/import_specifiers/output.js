@@ -0,0 +1,4 @@
+import "./Card.module.css";
+import "./Card.module.css";
+
+use("Card-module__root", "Card-module__root", "Card-module__foo-bar", "Card-module__title", "Card-module__foo-bar");
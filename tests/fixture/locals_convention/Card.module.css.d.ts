@@ -0,0 +1,11 @@
+declare const styles: {
+  readonly cardHeader: string;
+  readonly cardHeaderActive: string;
+  readonly title: string;
+};
+
+export default styles;
+
+export const cardHeader: string;
+export const cardHeaderActive: string;
+export const title: string;
@@ -0,0 +1,3 @@
+import styles from "./Card.module.css";
+
+const classes = [styles.cardHeader, styles.cardHeaderActive, styles.title];
@@ -0,0 +1,3 @@
+import "./Card.module.css";
+
+const classes = ["card-header_hashed", "card-header--active_hashed", "title_hashed"];
@@ -0,0 +1,9 @@
+import styles from "./Menu.module.css";
+
+export const menu = styles?.menu;
+export const active = styles?.["item-active"];
+
+export const hasMenu = "menu" in styles;
+export const hasActive = styles.hasOwnProperty("itemActive");
+export const hasOpen = Object.hasOwn(styles, "is-open");
+export const hasItem = Object.prototype.hasOwnProperty.call(styles, "item");
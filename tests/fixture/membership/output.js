@@ -0,0 +1,9 @@
+import "./Menu.module.css";
+
+export const menu = "Menu-module__menu";
+export const active = "Menu-module__item-active";
+
+export const hasMenu = true;
+export const hasActive = true;
+export const hasOpen = false;
+export const hasItem = false;
@@ -0,0 +1,4 @@
+import * as styles from "./Card.module.css";
+import { cardHeader, _class as active } from "./Card.module.css";
+
+const classes = [styles.cardHeader, styles._class, cardHeader, active];
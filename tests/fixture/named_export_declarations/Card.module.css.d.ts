@@ -0,0 +1,2 @@
+export const _class: string;
+export const cardHeader: string;
@@ -0,0 +1,8 @@
+import "./Card.module.css";
+import "./Card.module.css";
+const classes = [
+    "card-header_hashed",
+    "class_hashed",
+    "card-header_hashed",
+    "class_hashed"
+];
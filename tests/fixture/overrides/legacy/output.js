@@ -0,0 +1,4 @@
+import "./Card.module.css";
+import "../shared/Button.module.css";
+
+const classes = ["legacy__root", "shared_root"];
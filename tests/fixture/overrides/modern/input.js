@@ -0,0 +1,4 @@
+import card from "./Card.module.css";
+import button from "../shared/Button.module.css";
+
+const classes = [card.root, button.root];
@@ -0,0 +1,4 @@
+import "./Card.module.css";
+import "../shared/Button.module.css";
+
+const classes = ["root_c46631a2", "shared_root"];
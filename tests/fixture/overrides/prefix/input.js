@@ -0,0 +1,3 @@
+import spinner from "../shared/Spinner.module.css";
+
+const classes = [spinner.spinner, spinner.spin];
@@ -0,0 +1,5 @@
+import "../shared/Spinner.module.css";
+const classes = [
+    "spinner_cebc176c",
+    "spin_6ab13a"
+];
@@ -0,0 +1,4 @@
+import fromPackage from "@scope/ui/Button.module.css";
+import fromSource from "./packages/ui/Button.module.css";
+
+const classes = [fromPackage.root, fromSource.root];
@@ -0,0 +1,4 @@
+import "@scope/ui/Button.module.css";
+import "./packages/ui/Button.module.css";
+
+const classes = ["root_9efce213", "root_9efce213"];
@@ -0,0 +1,11 @@
+import { root } from "./utils";
+
+console.log(root);
+
+export { "is-active" as active } from "./Button.module.css";
+export { root as "main-root" } from "./Button.module.css";
+export { default as buttonStyles } from "./Button.module.css";
+export * as styles from "./Button.module.css";
+export * from "./Button.module.css";
+export { default } from "./Button.module.css";
+export { foo } from "./utils";
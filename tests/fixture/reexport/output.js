@@ -0,0 +1,24 @@
+import { root } from "./utils";
+console.log(root);
+import "./Button.module.css";
+const _active = "button_is-active";
+export { _active as active };
+const _export = "button_root";
+export { _export as "main-root" };
+const _buttonStyles = {
+    "is-active": "button_is-active",
+    root: "button_root"
+};
+export { _buttonStyles as buttonStyles };
+const _styles = {
+    "is-active": "button_is-active",
+    root: "button_root"
+};
+export { _styles as styles };
+const _root = "button_root";
+export { _root as root };
+export default {
+    "is-active": "button_is-active",
+    root: "button_root"
+};
+export { foo } from "./utils";
@@ -0,0 +1,6 @@
+import button from "@ui/Button.module.css";
+import theme from "@theme/Theme.module.css";
+import card from "kit/Card.module.css";
+import legacyCard from "~kit/Card.module.css";
+
+const classes = [button.root, theme.root, card.root, legacyCard.root];
@@ -0,0 +1,6 @@
+import "@ui/Button.module.css";
+import "@theme/Theme.module.css";
+import "kit/Card.module.css";
+import "~kit/Card.module.css";
+
+const classes = ["ui__root", "theme__root", "dist__root", "dist__root"];
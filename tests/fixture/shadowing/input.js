@@ -0,0 +1,7 @@
+import styles, { root } from "./Button.module.css";
+
+function label(styles, root) {
+  return format(styles, root);
+}
+
+use(styles.root, root, label);
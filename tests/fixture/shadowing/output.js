@@ -0,0 +1,5 @@
+import "./Button.module.css";
+function label(styles, root) {
+    return format(styles, root);
+}
+use("Button-module__root", "Button-module__root", label);
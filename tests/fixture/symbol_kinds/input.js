@@ -0,0 +1,11 @@
+import styles from "./Page.module.css";
+
+const names = [
+  styles.root,
+  styles.fadeIn,
+  styles["--brand"],
+  styles["--undeclared"],
+  styles.header,
+  styles["full-start"],
+  styles.page,
+];
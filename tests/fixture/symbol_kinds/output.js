@@ -0,0 +1,11 @@
+import "./Page.module.css";
+
+const names = [
+  "root__class",
+  "fadeIn__keyframes",
+  "--brand__var",
+  "--undeclared__var",
+  "header__grid",
+  "full-start__grid",
+  "page__container",
+];
@@ -0,0 +1,7 @@
+import styles from "./Button.module.css";
+
+export const Button = ({ active }: { active: boolean }) => (
+  <button className={active ? styles.active : styles.root}>
+    <span className={styles.label!}>{styles.icon as string}</span>
+  </button>
+);
@@ -0,0 +1,6 @@
+import "./Button.module.css";
+export const Button = ({ active }: {
+    active: boolean;
+})=><button className={active ? "tsx_active" : "tsx_root"}>
+    <span className={"tsx_label"!}>{"tsx_icon" as string}</span>
+  </button>;
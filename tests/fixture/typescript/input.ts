@@ -0,0 +1,11 @@
+import type { Styles } from "./Component.module.css";
+import styles, { type Names, title } from "./Component.module.css";
+
+const root = styles.root as const;
+const header = title satisfies string;
+
+const classes: Names = [styles["is-active"], title as string];
+
+function get(s: Styles): string {
+  return s.root;
+}
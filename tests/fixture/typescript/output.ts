@@ -0,0 +1,12 @@
+import type { Styles } from "./Component.module.css";
+import "./Component.module.css";
+import type { Names } from "./Component.module.css";
+const root = "ts_root" as const;
+const header = "ts_title" satisfies string;
+const classes: Names = [
+    "ts_is-active",
+    "ts_title" as string
+];
+function get(s: Styles): string {
+    return s.root;
+}
@@ -0,0 +1,9 @@
+import styles from "./Button.module.css";
+
+const theme = {
+  brand: styles.brand,
+  accent: styles.buttonAccent,
+  border: styles.border,
+};
+
+render(styles.root);
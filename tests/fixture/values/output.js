@@ -0,0 +1,9 @@
+import "./Button.module.css";
+
+const theme = {
+  brand: "#BF4040",
+  accent: "#4040BF",
+  border: "1px solid #BF4040",
+};
+
+render("Button-module__root");
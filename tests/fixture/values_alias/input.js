@@ -0,0 +1,6 @@
+import styles from "./Button.module.css";
+
+const theme = {
+  brand: styles.brand,
+  border: styles.border,
+};
@@ -0,0 +1,5 @@
+import "./Button.module.css";
+const theme = {
+    brand: "#BF4040",
+    border: "1px solid #BF4040"
+};
@@ -0,0 +1,63 @@
+//! Checks that names match the reference JS implementation.
+//!
+//! Vectors are generated by `tests/parity/generate.js` from
+//! [generic-names](https://github.com/css-modules/generic-names).
+
+use std::collections::BTreeSet;
+use std::{fs, path::PathBuf};
+
+use serde_json::Value;
+use swc_plugin_css_modules::generic_names::{Generator, Options};
+
+fn field<'a>(vector: &'a Value, name: &str) -> &'a str {
+    vector[name]
+        .as_str()
+        .unwrap_or_else(|| panic!("invalid `{name}` in {vector}"))
+}
+
+#[test]
+fn generic_names_parity() {
+    let corpus: Vec<Value> =
+        serde_json::from_str(&fs::read_to_string("tests/parity/corpus.json").unwrap())
+            .expect("invalid corpus");
+
+    // Paths are normalized, so vectors of every platform match on every platform
+    let platforms: BTreeSet<_> = corpus
+        .iter()
+        .map(|vector| field(vector, "platform"))
+        .collect();
+
+    assert_eq!(
+        platforms,
+        BTreeSet::from(["posix", "win32"]),
+        "vectors of every platform"
+    );
+
+    let mismatches: Vec<_> = corpus
+        .iter()
+        .filter_map(|vector| {
+            let generator = Generator::new_with_options(
+                field(vector, "pattern"),
+                Options {
+                    context: PathBuf::from(field(vector, "context")),
+                    hash_prefix: field(vector, "hash_prefix").to_string(),
+                },
+            );
+
+            let actual = generator.generate(
+                field(vector, "local"),
+                PathBuf::from(field(vector, "resource_path")),
+            );
+
+            (actual != field(vector, "expected")).then(|| format!("{vector}\n  actual: {actual}"))
+        })
+        .collect();
+
+    assert!(
+        mismatches.is_empty(),
+        "{} of {} vectors mismatch:\n{}",
+        mismatches.len(),
+        corpus.len(),
+        mismatches.join("\n")
+    );
+}
@@ -0,0 +1,114 @@
+// Generates `corpus.json` with names produced by the reference JS implementation.
+//
+//   npm i --no-save generic-names@4
+//   node tests/parity/generate.js
+const fs = require("fs");
+const path = require("path");
+const genericNames = require("generic-names");
+
+const ALGORITHMS = ["xxhash64", "md4", "md5", "sha1", "sha224", "sha256", "sha384", "sha512"];
+const DIGESTS = ["hex", "base32", "base64"];
+const LENGTHS = ["", ":6"];
+
+const PATTERNS = [
+  "[hash:base64:5]",
+  "[hash]",
+  "[local]",
+  "[name]__[local]___[hash:base64:5]",
+  "[folder]__[name]__[local]",
+  "[name].[ext]__[local]",
+  "[folder]-[local]-[hash:hex:8]",
+  "[contenthash:6]",
+  "prefix_[local]_[sha256:hash:hex:10]",
+];
+
+const POSIX_FILES = [
+  ["/home/user/project", "/home/user/project/src/components/Button/Button.module.css"],
+  ["/home/user/project", "/home/user/project/src/a.css"],
+  ["/home/user/project/src", "/home/user/project/node_modules/@ui/kit/dist/Card.module.scss"],
+  ["/home/user/проект", "/home/user/проект/стили/Кнопка.module.css"],
+  ["/srv/my app", "/srv/my app/src/Main Page/index.module.css"],
+  ["/work/app", "/work/app/src/a.b.c.module.css"],
+];
+
+const WIN32_FILES = [
+  ["C:\\Users\\dev\\project", "C:\\Users\\dev\\project\\src\\Button\\Button.module.css"],
+  ["C:\\Users\\dev\\project\\src", "C:\\Users\\dev\\project\\packages\\ui\\Card.module.css"],
+  ["\\\\server\\share\\app", "\\\\server\\share\\app\\src\\Page\\Page.module.css"],
+];
+
+const LOCALS = [
+  "root",
+  "Component__header--level-1",
+  "заголовок",
+  "0leading",
+  "--custom",
+  "a.b",
+  "ümlaut",
+];
+
+const HASH_PREFIXES = ["", "v1-", "--"];
+
+/** Runs `fn` with `path` methods of the platform, because generic-names uses the default `path`. */
+function withPlatform(platform, fn) {
+  const original = { ...path };
+
+  Object.assign(path, path[platform]);
+
+  try {
+    return fn();
+  } finally {
+    Object.assign(path, original);
+  }
+}
+
+function vector(platform, pattern, context, resourcePath, local, hashPrefix) {
+  const expected = withPlatform(platform, () =>
+    genericNames(pattern, { context, hashPrefix })(local, resourcePath),
+  );
+
+  return {
+    platform,
+    pattern,
+    context,
+    resource_path: resourcePath,
+    local,
+    hash_prefix: hashPrefix,
+    expected,
+  };
+}
+
+const corpus = [];
+
+// Every hash function and digest
+for (const algorithm of ALGORITHMS) {
+  for (const digest of DIGESTS) {
+    for (const length of LENGTHS) {
+      for (const [context, resourcePath] of POSIX_FILES.slice(0, 2)) {
+        for (const local of LOCALS.slice(0, 2)) {
+          const pattern = `[local]__[${algorithm}:hash:${digest}${length}]`;
+
+          corpus.push(vector("posix", pattern, context, resourcePath, local, ""));
+        }
+      }
+    }
+  }
+}
+
+// Every token with different paths, names and prefixes
+for (const [platform, files] of [
+  ["posix", POSIX_FILES],
+  ["win32", WIN32_FILES],
+]) {
+  PATTERNS.forEach((pattern, patternIndex) => {
+    files.forEach(([context, resourcePath], fileIndex) => {
+      LOCALS.forEach((local, localIndex) => {
+        const hashPrefix = HASH_PREFIXES[(patternIndex + fileIndex + localIndex) % HASH_PREFIXES.length];
+
+        corpus.push(vector(platform, pattern, context, resourcePath, local, hashPrefix));
+      });
+    });
+  });
+}
+
+fs.writeFileSync(path.join(__dirname, "corpus.json"), JSON.stringify(corpus, null, 2) + "\n");